@@ -0,0 +1,450 @@
+// Capture backend abstraction
+// Every capture command goes through one ordered chain of backends (xcap, grim, fake)
+// that is probed once, so fallback behavior is identical across commands
+// Set BEAUTYFULLSHOT_CAPTURE_BACKEND (e.g. "fake" or "grim,xcap") to override the chain
+
+use std::sync::OnceLock;
+
+use image::{Rgba, RgbaImage};
+use xcap::{Monitor, Window as XcapWindow};
+
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+/// Environment variable that overrides the probed backend chain
+const BACKEND_ENV_VAR: &str = "BEAUTYFULLSHOT_CAPTURE_BACKEND";
+
+/// A source of raw screen pixels
+pub trait CaptureBackend: Send + Sync {
+    /// Short identifier used in logs, env overrides and capture metadata
+    fn name(&self) -> &'static str;
+
+    /// Whether this backend can work in the current session (probed once)
+    fn is_available(&self) -> bool;
+
+    /// Capture a whole monitor by ID, or the primary monitor when `None`
+    fn capture_monitor(&self, monitor_id: Option<u32>) -> Result<RgbaImage, String>;
+
+    /// Capture a single window by ID
+    fn capture_window(&self, window_id: u32) -> Result<RgbaImage, String> {
+        let _ = window_id;
+        Err(format!("{} backend does not support window capture", self.name()))
+    }
+}
+
+/// Captured pixels together with the backend that produced them
+pub struct Capture {
+    pub image: RgbaImage,
+    pub backend: &'static str,
+}
+
+/// Find an xcap monitor by ID, or the primary monitor when `None`
+fn find_xcap_monitor(monitor_id: Option<u32>) -> Result<Monitor, String> {
+    let monitors = Monitor::all().map_err(|e| e.to_string())?;
+    match monitor_id {
+        Some(id) => monitors
+            .into_iter()
+            .find(|m| m.id().unwrap_or(0) == id)
+            .ok_or_else(|| "Monitor not found".to_string()),
+        None => monitors
+            .into_iter()
+            .find(|m| m.is_primary().unwrap_or(false))
+            .ok_or_else(|| "No primary monitor found".to_string()),
+    }
+}
+
+/// Native capture through the xcap crate (all desktop platforms)
+pub struct XcapBackend;
+
+impl CaptureBackend for XcapBackend {
+    fn name(&self) -> &'static str {
+        "xcap"
+    }
+
+    fn is_available(&self) -> bool {
+        Monitor::all().map(|m| !m.is_empty()).unwrap_or(false)
+    }
+
+    fn capture_monitor(&self, monitor_id: Option<u32>) -> Result<RgbaImage, String> {
+        let monitor = find_xcap_monitor(monitor_id)?;
+        monitor.capture_image().map_err(|e| e.to_string())
+    }
+
+    fn capture_window(&self, window_id: u32) -> Result<RgbaImage, String> {
+        let windows = XcapWindow::all().map_err(|e| e.to_string())?;
+        let window = windows
+            .into_iter()
+            .find(|w| w.id().unwrap_or(0) == window_id)
+            .ok_or("Window not found")?;
+
+        window.capture_image().map_err(|e| e.to_string())
+    }
+}
+
+/// Check if running on Wayland (Linux only)
+#[cfg(target_os = "linux")]
+pub fn is_wayland() -> bool {
+    std::env::var("WAYLAND_DISPLAY").is_ok()
+}
+
+/// Wayland-native capture through the grim command line tool
+#[cfg(target_os = "linux")]
+pub struct GrimBackend;
+
+#[cfg(target_os = "linux")]
+impl GrimBackend {
+    /// Run grim with the given arguments and decode the PNG it writes to stdout
+    fn run(args: &[&str]) -> Result<RgbaImage, String> {
+        let output = Command::new("grim")
+            .args(args)
+            .arg("-")
+            .output()
+            .map_err(|e| format!("Failed to run grim: {}. Install grim for Wayland support.", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("grim failed: {}", stderr));
+        }
+
+        image::load_from_memory(&output.stdout)
+            .map(|img| img.to_rgba8())
+            .map_err(|e| format!("Failed to decode grim output: {}", e))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl CaptureBackend for GrimBackend {
+    fn name(&self) -> &'static str {
+        "grim"
+    }
+
+    fn is_available(&self) -> bool {
+        is_wayland()
+            && Command::new("which")
+                .arg("grim")
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+    }
+
+    fn capture_monitor(&self, monitor_id: Option<u32>) -> Result<RgbaImage, String> {
+        // grim selects outputs by name; resolve it through xcap when listing works
+        let output_name = find_xcap_monitor(monitor_id)
+            .ok()
+            .and_then(|m| m.name().ok())
+            .filter(|name| !name.is_empty());
+
+        match (output_name, monitor_id) {
+            (Some(name), _) => Self::run(&["-o", &name]),
+            // Without monitor names, grim can still capture the whole layout
+            (None, None) => Self::run(&[]),
+            (None, Some(_)) => Err("grim could not resolve the monitor output name".to_string()),
+        }
+    }
+}
+
+/// In-memory backend producing a deterministic test pattern
+/// Lets the capture layer run on headless machines without a display server
+pub struct FakeBackend {
+    width: u32,
+    height: u32,
+}
+
+impl FakeBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    fn pattern(&self, seed: u32) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            Rgba([
+                (x * 255 / self.width.max(1)) as u8,
+                (y * 255 / self.height.max(1)) as u8,
+                (seed % 256) as u8,
+                255,
+            ])
+        })
+    }
+}
+
+impl Default for FakeBackend {
+    fn default() -> Self {
+        Self::new(1280, 800)
+    }
+}
+
+impl CaptureBackend for FakeBackend {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn capture_monitor(&self, monitor_id: Option<u32>) -> Result<RgbaImage, String> {
+        Ok(self.pattern(monitor_id.unwrap_or(0)))
+    }
+
+    fn capture_window(&self, window_id: u32) -> Result<RgbaImage, String> {
+        Ok(self.pattern(window_id))
+    }
+}
+
+/// Build a backend by name (used by the env override)
+fn backend_by_name(name: &str) -> Option<Box<dyn CaptureBackend>> {
+    match name.trim().to_lowercase().as_str() {
+        "xcap" => Some(Box::new(XcapBackend)),
+        #[cfg(target_os = "linux")]
+        "grim" => Some(Box::new(GrimBackend)),
+        "fake" => Some(Box::new(FakeBackend::default())),
+        _ => None,
+    }
+}
+
+/// Default backend order: native xcap first, then platform-specific fallbacks
+fn default_candidates() -> Vec<Box<dyn CaptureBackend>> {
+    #[allow(unused_mut)]
+    let mut candidates: Vec<Box<dyn CaptureBackend>> = vec![Box::new(XcapBackend)];
+    #[cfg(target_os = "linux")]
+    candidates.push(Box::new(GrimBackend));
+    candidates
+}
+
+/// Backends named in a comma-separated env override, skipping unknown names
+fn override_candidates(list: &str) -> Vec<Box<dyn CaptureBackend>> {
+    list.split(',')
+        .filter(|name| !name.trim().is_empty())
+        .filter_map(|name| {
+            let backend = backend_by_name(name);
+            if backend.is_none() {
+                println!("Unknown capture backend '{}' in {}", name.trim(), BACKEND_ENV_VAR);
+            }
+            backend
+        })
+        .collect()
+}
+
+/// Ordered chain of available capture backends
+pub struct CaptureRegistry {
+    chain: Vec<Box<dyn CaptureBackend>>,
+}
+
+impl CaptureRegistry {
+    /// Probe candidate backends and keep the available ones, in order
+    pub fn probe() -> Self {
+        let candidates = match std::env::var(BACKEND_ENV_VAR) {
+            Ok(list) if !list.trim().is_empty() => override_candidates(&list),
+            _ => default_candidates(),
+        };
+        Self::from_candidates(candidates)
+    }
+
+    /// Keep the available candidates, in order
+    fn from_candidates(candidates: Vec<Box<dyn CaptureBackend>>) -> Self {
+        let chain: Vec<Box<dyn CaptureBackend>> =
+            candidates.into_iter().filter(|b| b.is_available()).collect();
+
+        println!(
+            "Capture backends: [{}]",
+            chain.iter().map(|b| b.name()).collect::<Vec<_>>().join(", ")
+        );
+
+        Self { chain }
+    }
+
+    /// Names of the backends in the chain, in fallback order
+    pub fn backend_names(&self) -> Vec<&'static str> {
+        self.chain.iter().map(|b| b.name()).collect()
+    }
+
+    /// Capture a monitor by ID (primary when `None`), falling back through the chain
+    pub fn capture_monitor(&self, monitor_id: Option<u32>) -> Result<Capture, String> {
+        self.first_success(|backend| backend.capture_monitor(monitor_id))
+    }
+
+    /// Capture a window by ID, falling back through the chain
+    pub fn capture_window(&self, window_id: u32) -> Result<Capture, String> {
+        self.first_success(|backend| backend.capture_window(window_id))
+    }
+
+    /// Try each backend in order and return the first non-empty image
+    fn first_success<F>(&self, capture: F) -> Result<Capture, String>
+    where
+        F: Fn(&dyn CaptureBackend) -> Result<RgbaImage, String>,
+    {
+        if self.chain.is_empty() {
+            return Err("No capture backend available".to_string());
+        }
+
+        let mut errors = Vec::new();
+        for backend in &self.chain {
+            match capture(backend.as_ref()) {
+                // An empty image means the OS silently denied capture
+                Ok(image) if image.width() == 0 || image.height() == 0 => {
+                    errors.push((
+                        backend.name(),
+                        "Screen recording permission not granted".to_string(),
+                    ));
+                }
+                Ok(image) => {
+                    return Ok(Capture {
+                        image,
+                        backend: backend.name(),
+                    })
+                }
+                Err(e) => {
+                    println!("{} capture failed: {}", backend.name(), e);
+                    errors.push((backend.name(), e));
+                }
+            }
+        }
+
+        // Single-backend chains keep the original, unprefixed error message
+        if errors.len() == 1 {
+            return Err(errors.remove(0).1);
+        }
+        Err(errors
+            .iter()
+            .map(|(name, e)| format!("{}: {}", name, e))
+            .collect::<Vec<_>>()
+            .join("; "))
+    }
+}
+
+static REGISTRY: OnceLock<CaptureRegistry> = OnceLock::new();
+
+/// Global backend registry, probed on first use
+pub fn registry() -> &'static CaptureRegistry {
+    REGISTRY.get_or_init(CaptureRegistry::probe)
+}
+
+/// List the active capture backends in fallback order
+#[tauri::command]
+pub fn get_capture_backends() -> Vec<String> {
+    registry()
+        .backend_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backend with a scripted result, for exercising the fallback chain
+    struct StubBackend {
+        name: &'static str,
+        available: bool,
+        result: Result<(u32, u32), &'static str>,
+    }
+
+    impl StubBackend {
+        fn boxed(name: &'static str, result: Result<(u32, u32), &'static str>) -> Box<dyn CaptureBackend> {
+            Box::new(Self { name, available: true, result })
+        }
+    }
+
+    impl CaptureBackend for StubBackend {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn is_available(&self) -> bool {
+            self.available
+        }
+
+        fn capture_monitor(&self, _monitor_id: Option<u32>) -> Result<RgbaImage, String> {
+            self.result
+                .map(|(width, height)| RgbaImage::new(width, height))
+                .map_err(String::from)
+        }
+    }
+
+    #[test]
+    fn falls_back_to_next_backend() {
+        let registry = CaptureRegistry::from_candidates(vec![
+            StubBackend::boxed("broken", Err("no display")),
+            Box::new(FakeBackend::new(4, 3)),
+        ]);
+        let capture = registry.capture_monitor(None).unwrap();
+        assert_eq!(capture.backend, "fake");
+        assert_eq!(capture.image.dimensions(), (4, 3));
+    }
+
+    #[test]
+    fn first_working_backend_wins() {
+        let registry = CaptureRegistry::from_candidates(vec![
+            StubBackend::boxed("first", Ok((2, 2))),
+            Box::new(FakeBackend::new(4, 3)),
+        ]);
+        assert_eq!(registry.capture_monitor(Some(1)).unwrap().backend, "first");
+    }
+
+    #[test]
+    fn unavailable_backends_are_dropped() {
+        let registry = CaptureRegistry::from_candidates(vec![
+            Box::new(StubBackend { name: "missing", available: false, result: Ok((2, 2)) }),
+            Box::new(FakeBackend::default()),
+        ]);
+        assert_eq!(registry.backend_names(), vec!["fake"]);
+    }
+
+    #[test]
+    fn empty_image_is_rejected() {
+        let registry = CaptureRegistry::from_candidates(vec![StubBackend::boxed("denied", Ok((0, 0)))]);
+        let error = registry.capture_monitor(None).err().unwrap();
+        assert_eq!(error, "Screen recording permission not granted");
+
+        let registry = CaptureRegistry::from_candidates(vec![
+            StubBackend::boxed("denied", Ok((100, 0))),
+            Box::new(FakeBackend::new(4, 3)),
+        ]);
+        assert_eq!(registry.capture_monitor(None).unwrap().backend, "fake");
+    }
+
+    #[test]
+    fn errors_from_every_backend_are_reported() {
+        let registry = CaptureRegistry::from_candidates(vec![
+            StubBackend::boxed("a", Err("first failed")),
+            StubBackend::boxed("b", Err("second failed")),
+        ]);
+        assert_eq!(
+            registry.capture_monitor(None).err().unwrap(),
+            "a: first failed; b: second failed"
+        );
+    }
+
+    #[test]
+    fn empty_chain_is_an_error() {
+        let registry = CaptureRegistry::from_candidates(Vec::new());
+        assert!(registry.capture_window(1).is_err());
+    }
+
+    #[test]
+    fn default_window_capture_is_unsupported() {
+        let registry = CaptureRegistry::from_candidates(vec![StubBackend::boxed("monitor-only", Ok((2, 2)))]);
+        let error = registry.capture_window(7).err().unwrap();
+        assert!(error.contains("does not support window capture"), "{}", error);
+    }
+
+    #[test]
+    fn override_list_keeps_order_and_skips_unknown_names() {
+        let names = |list: &str| -> Vec<&'static str> {
+            override_candidates(list).iter().map(|b| b.name()).collect()
+        };
+        assert_eq!(names("fake, XCAP"), vec!["fake", "xcap"]);
+        assert_eq!(names("bogus,fake,,"), vec!["fake"]);
+        assert!(names("bogus").is_empty());
+    }
+
+    #[test]
+    fn fake_backend_pattern_depends_on_id() {
+        let backend = FakeBackend::new(8, 8);
+        let monitor = backend.capture_monitor(Some(3)).unwrap();
+        let window = backend.capture_window(9).unwrap();
+        assert_eq!(monitor.dimensions(), (8, 8));
+        assert_ne!(monitor, window);
+    }
+}
@@ -0,0 +1,425 @@
+// In-memory capture store and binary transport
+// Captures are kept as raw RGBA keyed by id, with metadata, reference counts and an
+// LRU memory cap, and served to the webview through the capture:// URI scheme
+// (or raw IPC responses), avoiding base64 PNG strings over IPC
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use image::RgbaImage;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{ipc, Manager, Runtime, State, UriSchemeContext, UriSchemeResponder};
+
+use crate::screenshot::image_to_png_bytes;
+
+/// URI scheme the webview uses to load stored captures
+pub const CAPTURE_SCHEME: &str = "capture";
+
+/// Header carrying the other arguments of a raw RGBA upload, as percent-encoded JSON
+pub const RGBA_ARGS_HEADER: &str = "x-rgba-args";
+
+/// Maximum raw pixel memory held by the store (512MB) before LRU eviction
+const MAX_STORE_BYTES: usize = 512 * 1024 * 1024;
+
+/// What kind of capture produced an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureKind {
+    Monitor,
+    Region,
+    Window,
+    Thumbnail,
+    /// Existing image opened from a file or pasted from the clipboard
+    Imported,
+}
+
+/// Where and when a capture was taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureMetadata {
+    pub kind: CaptureKind,
+    pub monitor_id: Option<u32>,
+    pub monitor_name: Option<String>,
+    pub window_id: Option<u32>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub scale_factor: f32,
+    /// Unix timestamp in milliseconds
+    pub captured_at: u64,
+    pub backend: String,
+    pub cursor_x: Option<i32>,
+    pub cursor_y: Option<i32>,
+}
+
+impl CaptureMetadata {
+    /// Metadata with only the kind, backend and timestamp filled in
+    pub fn new(kind: CaptureKind, backend: &str) -> Self {
+        Self {
+            kind,
+            monitor_id: None,
+            monitor_name: None,
+            window_id: None,
+            app_name: None,
+            window_title: None,
+            scale_factor: 1.0,
+            captured_at: now_millis(),
+            backend: backend.to_string(),
+            cursor_x: None,
+            cursor_y: None,
+        }
+    }
+}
+
+/// Current time as Unix milliseconds
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Reference to a stored capture returned to the frontend instead of pixel data
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureRef {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Summary of a stored capture for listing
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureInfo {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: usize,
+    pub ref_count: u32,
+    pub metadata: CaptureMetadata,
+}
+
+struct CaptureEntry {
+    image: Arc<RgbaImage>,
+    metadata: CaptureMetadata,
+    ref_count: u32,
+    /// Monotonic access tick for LRU eviction
+    last_access: u64,
+}
+
+impl CaptureEntry {
+    fn byte_size(&self) -> usize {
+        self.image.as_raw().len()
+    }
+}
+
+#[derive(Default)]
+struct StoreInner {
+    entries: HashMap<String, CaptureEntry>,
+    total_bytes: usize,
+    tick: u64,
+}
+
+impl StoreInner {
+    fn touch(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, id: &str) -> Option<CaptureEntry> {
+        let entry = self.entries.remove(id)?;
+        self.total_bytes -= entry.byte_size();
+        Some(entry)
+    }
+
+    /// Evict least recently used captures until under the cap, never touching `keep`
+    fn evict_to_fit(&mut self, max_bytes: usize, keep: &str) {
+        while self.total_bytes > max_bytes {
+            let victim = self
+                .entries
+                .iter()
+                .filter(|(id, _)| id.as_str() != keep)
+                .min_by_key(|(_, e)| e.last_access)
+                .map(|(id, _)| id.clone());
+
+            match victim {
+                Some(id) => {
+                    println!("Capture store over budget, evicting {}", id);
+                    self.remove(&id);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Captured images keyed by id, managed as Tauri state
+pub struct CaptureStore {
+    next_id: AtomicU64,
+    max_bytes: usize,
+    inner: Mutex<StoreInner>,
+}
+
+impl Default for CaptureStore {
+    fn default() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            max_bytes: MAX_STORE_BYTES,
+            inner: Mutex::new(StoreInner::default()),
+        }
+    }
+}
+
+impl CaptureStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, StoreInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Store an image with one reference held by the caller
+    pub fn insert(&self, image: RgbaImage, metadata: CaptureMetadata) -> CaptureRef {
+        let id = format!("capture-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let capture_ref = CaptureRef {
+            id: id.clone(),
+            width: image.width(),
+            height: image.height(),
+        };
+
+        let mut inner = self.lock();
+        let last_access = inner.touch();
+        let entry = CaptureEntry {
+            image: Arc::new(image),
+            metadata,
+            ref_count: 1,
+            last_access,
+        };
+        inner.total_bytes += entry.byte_size();
+        inner.entries.insert(id.clone(), entry);
+        inner.evict_to_fit(self.max_bytes, &id);
+
+        capture_ref
+    }
+
+    /// Get a stored image by id, marking it as recently used
+    pub fn get(&self, id: &str) -> Option<Arc<RgbaImage>> {
+        let mut inner = self.lock();
+        let tick = inner.touch();
+        let entry = inner.entries.get_mut(id)?;
+        entry.last_access = tick;
+        Some(entry.image.clone())
+    }
+
+    /// Get the metadata of a stored capture
+    pub fn metadata(&self, id: &str) -> Option<CaptureMetadata> {
+        self.lock().entries.get(id).map(|e| e.metadata.clone())
+    }
+
+    /// Add a reference to a stored capture
+    pub fn retain(&self, id: &str) -> bool {
+        match self.lock().entries.get_mut(id) {
+            Some(entry) => {
+                entry.ref_count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop one reference; the capture is freed when no references remain
+    pub fn release(&self, id: &str) -> bool {
+        let mut inner = self.lock();
+        let Some(entry) = inner.entries.get_mut(id) else {
+            return false;
+        };
+        entry.ref_count = entry.ref_count.saturating_sub(1);
+        if entry.ref_count == 0 {
+            inner.remove(id);
+        }
+        true
+    }
+
+    /// Summaries of all stored captures, newest first
+    pub fn list(&self) -> Vec<CaptureInfo> {
+        let inner = self.lock();
+        let mut infos: Vec<CaptureInfo> = inner
+            .entries
+            .iter()
+            .map(|(id, e)| CaptureInfo {
+                id: id.clone(),
+                width: e.image.width(),
+                height: e.image.height(),
+                byte_size: e.byte_size(),
+                ref_count: e.ref_count,
+                metadata: e.metadata.clone(),
+            })
+            .collect();
+        infos.sort_by_key(|info| std::cmp::Reverse(info.metadata.captured_at));
+        infos
+    }
+}
+
+/// Build a plain-text error response for the capture protocol
+fn error_response(status: StatusCode, message: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(message.as_bytes().to_vec())
+        .unwrap_or_default()
+}
+
+/// Serve `capture://localhost/<id>` (or `http://capture.localhost/<id>` on Windows) as PNG
+/// Encoding runs on a worker thread so large captures don't block the event loop
+pub fn handle_capture_protocol<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let id = request.uri().path().trim_start_matches('/').to_string();
+    let image = ctx.app_handle().state::<CaptureStore>().get(&id);
+
+    std::thread::spawn(move || {
+        let response = match image {
+            None => error_response(StatusCode::NOT_FOUND, "Capture not found"),
+            Some(image) => match image_to_png_bytes(&image) {
+                Ok(png) => Response::builder()
+                    .header(header::CONTENT_TYPE, "image/png")
+                    .header(header::CACHE_CONTROL, "no-store")
+                    .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                    .body(png)
+                    .unwrap_or_default(),
+                Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e),
+            },
+        };
+        responder.respond(response);
+    });
+}
+
+/// Size of a raw RGBA upload plus the command's own arguments
+#[derive(Deserialize)]
+struct RgbaArgs<T> {
+    width: u32,
+    height: u32,
+    #[serde(flatten)]
+    args: T,
+}
+
+/// Read a raw RGBA upload: the pixels are the binary IPC body, and width, height and
+/// the command's other arguments come from the RGBA_ARGS_HEADER header
+pub fn rgba_from_request<T: DeserializeOwned>(request: &ipc::Request<'_>) -> Result<(RgbaImage, T), String> {
+    let ipc::InvokeBody::Raw(pixels) = request.body() else {
+        return Err("Expected raw RGBA pixels as the request body".to_string());
+    };
+    let header = request
+        .headers()
+        .get(RGBA_ARGS_HEADER)
+        .ok_or_else(|| format!("Missing {} header", RGBA_ARGS_HEADER))?
+        .to_str()
+        .map_err(|e| format!("Invalid {} header: {}", RGBA_ARGS_HEADER, e))?;
+    let json = percent_encoding::percent_decode_str(header)
+        .decode_utf8()
+        .map_err(|e| format!("Invalid {} header: {}", RGBA_ARGS_HEADER, e))?;
+    let RgbaArgs { width, height, args } =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid {} header: {}", RGBA_ARGS_HEADER, e))?;
+    let image = RgbaImage::from_raw(width, height, pixels.clone())
+        .ok_or("Pixel data does not match width and height")?;
+    Ok((image, args))
+}
+
+/// Get a stored capture as raw RGBA bytes (width/height come from its CaptureRef)
+#[tauri::command]
+pub fn get_capture_rgba(store: State<'_, CaptureStore>, id: String) -> Result<ipc::Response, String> {
+    let image = store.get(&id).ok_or("Capture not found")?;
+    Ok(ipc::Response::new(image.as_raw().clone()))
+}
+
+/// Get a stored capture encoded as PNG bytes
+#[tauri::command]
+pub fn get_capture_png(store: State<'_, CaptureStore>, id: String) -> Result<ipc::Response, String> {
+    let image = store.get(&id).ok_or("Capture not found")?;
+    Ok(ipc::Response::new(image_to_png_bytes(&image)?))
+}
+
+/// Get metadata for a stored capture
+#[tauri::command]
+pub fn get_capture_metadata(
+    store: State<'_, CaptureStore>,
+    id: String,
+) -> Result<CaptureMetadata, String> {
+    store.metadata(&id).ok_or_else(|| "Capture not found".to_string())
+}
+
+/// List all captures currently held in memory
+#[tauri::command]
+pub fn list_captures(store: State<'_, CaptureStore>) -> Vec<CaptureInfo> {
+    store.list()
+}
+
+/// Add a reference to a stored capture (e.g. when a second view shows it)
+#[tauri::command]
+pub fn retain_capture(store: State<'_, CaptureStore>, id: String) -> bool {
+    store.retain(&id)
+}
+
+/// Release a stored capture once the frontend no longer needs it
+#[tauri::command]
+pub fn release_capture(store: State<'_, CaptureStore>, id: String) -> bool {
+    store.release(&id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Store that holds at most `captures` 4x4 images
+    fn small_store(captures: usize) -> CaptureStore {
+        CaptureStore {
+            max_bytes: captures * 4 * 4 * 4,
+            ..CaptureStore::default()
+        }
+    }
+
+    fn insert(store: &CaptureStore) -> String {
+        let metadata = CaptureMetadata::new(CaptureKind::Monitor, "fake");
+        store.insert(RgbaImage::new(4, 4), metadata).id
+    }
+
+    #[test]
+    fn evicts_least_recently_used_over_budget() {
+        let store = small_store(2);
+        let first = insert(&store);
+        let second = insert(&store);
+        store.get(&first);
+        let third = insert(&store);
+
+        assert!(store.get(&first).is_some());
+        assert!(store.get(&second).is_none());
+        assert!(store.get(&third).is_some());
+    }
+
+    #[test]
+    fn released_captures_free_their_budget() {
+        let store = small_store(2);
+        let released = insert(&store);
+        let kept = insert(&store);
+        assert!(store.release(&released));
+        let newest = insert(&store);
+
+        assert!(store.get(&kept).is_some());
+        assert!(store.get(&newest).is_some());
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn release_frees_after_last_reference() {
+        let store = CaptureStore::default();
+        let id = insert(&store);
+        assert!(store.retain(&id));
+        assert!(store.release(&id));
+        assert!(store.get(&id).is_some());
+        assert!(store.release(&id));
+        assert!(store.get(&id).is_none());
+        assert!(!store.release(&id));
+    }
+}
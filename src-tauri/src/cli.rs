@@ -0,0 +1,403 @@
+// Headless command-line interface for scripted captures
+// `beautyfullshot capture ...` captures, saves and/or copies, then exits
+// without opening the main window; no arguments launches the GUI as usual
+
+use std::path::Path;
+use std::time::Duration;
+
+use image::RgbaImage;
+
+use crate::export::{self, ExportSpec};
+use crate::multi_monitor;
+use crate::virtual_desktop::{self, DesktopCaptureOptions, DpiScale, GapFill};
+use crate::window_frame::{self, WindowCaptureOptions, WindowFraming};
+use crate::window_match::{self, WindowQuery};
+use crate::{capture_backend, file_ops, permissions, rich_clipboard, screenshot};
+
+/// Exit codes distinguishing failure classes for scripts
+pub mod exit_code {
+    pub const SUCCESS: i32 = 0;
+    pub const CAPTURE_FAILED: i32 = 1;
+    pub const USAGE: i32 = 2;
+    pub const PERMISSION_DENIED: i32 = 3;
+    pub const NOT_FOUND: i32 = 4;
+    pub const IO_ERROR: i32 = 5;
+}
+
+/// How long the CLI keeps serving the clipboard on Linux after copying
+const CLIPBOARD_HOLD: Duration = Duration::from_secs(30);
+
+const USAGE: &str = "\
+Usage:
+  beautyfullshot capture [options]   Capture without opening the editor
+  beautyfullshot list-monitors       Print monitors (index, id, name, geometry)
+  beautyfullshot list-windows        Print capturable windows (id, app, title)
+  beautyfullshot help                Show this message
+
+Capture options:
+  --monitor <N>          Monitor index from list-monitors (default: primary)
+  --all-screens          Capture all monitors stitched into one image
+  --gap-fill <FILL>      Fill between monitors: transparent (default), black, desktop
+  --dpi <SCALE>          Mixed-DPI resampling target: highest (default), lowest
+  --each-monitor         Capture every monitor to its own file in the --out folder,
+                         named after the monitor
+  --window-title <TEXT>  Capture the best window whose title contains TEXT
+  --window-app <TEXT>    Capture the best window whose app name contains TEXT
+  --window-pid <PID>     Only consider windows of process PID
+  --window-regex         Match --window-title/--window-app as regular expressions
+  --focused-window       Capture the focused window
+  --region <X,Y,W,H>     Crop to a region relative to the captured monitor/window/screens
+  --window-frame <MODE>  Window framing: native (default), content, frame, shadow
+  --opaque               Flatten transparent window edges and shadow onto white
+  --out <PATH>           Save to PATH (format from extension, default PNG)
+  --clipboard            Copy the capture to the clipboard
+
+Exit codes: 0 ok, 1 capture failed, 2 usage, 3 permission denied,
+            4 monitor/window not found, 5 I/O error";
+
+/// Error carrying the exit code it maps to
+#[derive(Debug)]
+pub struct CliError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl CliError {
+    fn usage(message: impl Into<String>) -> Self {
+        Self {
+            code: exit_code::USAGE,
+            message: message.into(),
+        }
+    }
+
+    fn io(message: impl Into<String>) -> Self {
+        Self {
+            code: exit_code::IO_ERROR,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            code: exit_code::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+
+    /// Classify a capture error string from the screenshot layer
+    fn from_capture(message: String) -> Self {
+        let lower = message.to_lowercase();
+        let code = if lower.contains("permission") {
+            exit_code::PERMISSION_DENIED
+        } else if lower.contains("not found") {
+            exit_code::NOT_FOUND
+        } else {
+            exit_code::CAPTURE_FAILED
+        };
+        Self { code, message }
+    }
+}
+
+/// Parsed `capture` subcommand options
+#[derive(Debug, Default)]
+pub struct CaptureArgs {
+    pub monitor: Option<usize>,
+    pub all_screens: bool,
+    pub each_monitor: bool,
+    pub desktop_options: DesktopCaptureOptions,
+    pub window: WindowQuery,
+    pub region: Option<(i32, i32, u32, u32)>,
+    pub window_options: WindowCaptureOptions,
+    pub out: Option<String>,
+    pub clipboard: bool,
+}
+
+/// A headless action requested on the command line
+#[derive(Debug)]
+pub enum CliCommand {
+    Capture(CaptureArgs),
+    ListMonitors,
+    ListWindows,
+    Help,
+}
+
+/// Parse "x,y,w,h" into a region
+fn parse_region(value: &str) -> Result<(i32, i32, u32, u32), CliError> {
+    let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+    let invalid = || CliError::usage(format!("Invalid --region '{}', expected X,Y,W,H", value));
+    if parts.len() != 4 {
+        return Err(invalid());
+    }
+    Ok((
+        parts[0].parse().map_err(|_| invalid())?,
+        parts[1].parse().map_err(|_| invalid())?,
+        parts[2].parse().map_err(|_| invalid())?,
+        parts[3].parse().map_err(|_| invalid())?,
+    ))
+}
+
+fn parse_capture_args(args: &[String]) -> Result<CaptureArgs, CliError> {
+    let mut parsed = CaptureArgs::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| CliError::usage(format!("{} requires a value", name)))
+        };
+
+        match arg.as_str() {
+            "--monitor" => {
+                let raw = value("--monitor")?;
+                let index = raw
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|i| *i >= 1)
+                    .ok_or_else(|| CliError::usage(format!("Invalid --monitor '{}'", raw)))?;
+                parsed.monitor = Some(index);
+            }
+            "--all-screens" => parsed.all_screens = true,
+            "--each-monitor" => parsed.each_monitor = true,
+            "--gap-fill" => {
+                let raw = value("--gap-fill")?;
+                parsed.desktop_options.gap_fill = GapFill::from_name(&raw)
+                    .ok_or_else(|| CliError::usage(format!("Invalid --gap-fill '{}'", raw)))?;
+            }
+            "--dpi" => {
+                let raw = value("--dpi")?;
+                parsed.desktop_options.dpi = DpiScale::from_name(&raw)
+                    .ok_or_else(|| CliError::usage(format!("Invalid --dpi '{}'", raw)))?;
+            }
+            "--window-title" => parsed.window.title = Some(value("--window-title")?),
+            "--window-app" => parsed.window.app_name = Some(value("--window-app")?),
+            "--window-pid" => {
+                let raw = value("--window-pid")?;
+                let pid = raw
+                    .parse::<u32>()
+                    .map_err(|_| CliError::usage(format!("Invalid --window-pid '{}'", raw)))?;
+                parsed.window.pid = Some(pid);
+            }
+            "--window-regex" => parsed.window.regex = true,
+            "--focused-window" => parsed.window.focused = Some(true),
+            "--region" => parsed.region = Some(parse_region(&value("--region")?)?),
+            "--window-frame" => {
+                let raw = value("--window-frame")?;
+                parsed.window_options.framing = WindowFraming::from_name(&raw)
+                    .ok_or_else(|| CliError::usage(format!("Invalid --window-frame '{}'", raw)))?;
+            }
+            "--opaque" => parsed.window_options.transparent = false,
+            "--out" => parsed.out = Some(value("--out")?),
+            "--clipboard" => parsed.clipboard = true,
+            other => return Err(CliError::usage(format!("Unknown option '{}'", other))),
+        }
+    }
+
+    let targets = [
+        parsed.monitor.is_some(),
+        parsed.all_screens,
+        parsed.each_monitor,
+        !parsed.window.is_empty(),
+    ];
+    if targets.iter().filter(|t| **t).count() > 1 {
+        return Err(CliError::usage(
+            "--monitor, --all-screens, --each-monitor and --window-* options are mutually exclusive",
+        ));
+    }
+    if parsed.each_monitor && (parsed.out.is_none() || parsed.clipboard || parsed.region.is_some()) {
+        return Err(CliError::usage("--each-monitor needs --out <FOLDER> and no --clipboard or --region"));
+    }
+    if parsed.out.is_none() && !parsed.clipboard {
+        return Err(CliError::usage("Nothing to do: pass --out <PATH> and/or --clipboard"));
+    }
+    Ok(parsed)
+}
+
+/// Parse process arguments (without the program name)
+/// Returns `Ok(None)` when no headless subcommand was given and the GUI should start
+pub fn parse(args: &[String]) -> Result<Option<CliCommand>, CliError> {
+    let Some(first) = args.first() else {
+        return Ok(None);
+    };
+
+    match first.as_str() {
+        "capture" => Ok(Some(CliCommand::Capture(parse_capture_args(&args[1..])?))),
+        "list-monitors" => Ok(Some(CliCommand::ListMonitors)),
+        "list-windows" => Ok(Some(CliCommand::ListWindows)),
+        "help" | "--help" | "-h" => Ok(Some(CliCommand::Help)),
+        // Anything else (e.g. OS-provided flags) is left to the GUI
+        _ => Ok(None),
+    }
+}
+
+/// Capture the requested monitor or window
+fn capture_image(args: &CaptureArgs) -> Result<RgbaImage, CliError> {
+    let registry = capture_backend::registry();
+
+    let image = if !args.window.is_empty() {
+        let windows = screenshot::get_windows().map_err(CliError::from_capture)?;
+        let window = window_match::rank_windows(windows, &args.window)
+            .map_err(CliError::usage)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CliError::not_found("No window matches the --window-* options"))?;
+        window_frame::capture(window.id, &args.window_options)
+            .map_err(CliError::from_capture)?
+            .image
+    } else if args.all_screens {
+        virtual_desktop::capture_desktop(&args.desktop_options)
+            .map_err(CliError::from_capture)?
+            .image
+    } else if let Some(index) = args.monitor {
+        let monitors = screenshot::get_monitors().map_err(CliError::from_capture)?;
+        let monitor = monitors
+            .get(index - 1)
+            .ok_or_else(|| CliError::not_found(format!("Monitor {} not found", index)))?;
+        registry
+            .capture_monitor(Some(monitor.id))
+            .map_err(CliError::from_capture)?
+            .image
+    } else {
+        registry.capture_monitor(None).map_err(CliError::from_capture)?.image
+    };
+
+    match args.region {
+        Some((x, y, width, height)) => {
+            screenshot::crop_region(&image, x, y, width, height).map_err(CliError::usage)
+        }
+        None => Ok(image),
+    }
+}
+
+/// Encode an image for the output path (format inferred from extension, PNG fallback)
+fn encode_for_path(image: &RgbaImage, path: &str) -> Result<Vec<u8>, CliError> {
+    export::encode(image, &ExportSpec::for_path(path)).map_err(CliError::usage)
+}
+
+/// Save one file per monitor, printing each saved path
+/// Monitors that fail are listed on stderr; the exit code reflects the last failure
+fn run_each_monitor(directory: &str) -> Result<(), CliError> {
+    let results = multi_monitor::save_each_monitor(Path::new(directory), &multi_monitor::default_spec())
+        .map_err(CliError::from_capture)?;
+
+    let mut failure = None;
+    for result in results {
+        match (result.path, result.error) {
+            (Some(path), _) => println!("{}", path),
+            (None, error) => {
+                let message = format!(
+                    "Monitor {} ({}): {}",
+                    result.monitor_id,
+                    result.monitor_name,
+                    error.unwrap_or_default()
+                );
+                eprintln!("{}", message);
+                failure = Some(CliError::from_capture(message));
+            }
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+fn run_capture(args: &CaptureArgs) -> Result<(), CliError> {
+    if !permissions::check_screen_permission() {
+        return Err(CliError {
+            code: exit_code::PERMISSION_DENIED,
+            message: "Screen recording permission not granted".to_string(),
+        });
+    }
+
+    if args.each_monitor {
+        return run_each_monitor(args.out.as_deref().unwrap_or_default());
+    }
+
+    let image = capture_image(args)?;
+
+    let saved = match &args.out {
+        Some(out) => {
+            let bytes = encode_for_path(&image, out)?;
+            let saved = file_ops::write_file_with_limit(out, &bytes, export::max_file_size(&image))
+                .map_err(CliError::io)?;
+            println!("{}", saved);
+            Some(saved)
+        }
+        None => None,
+    };
+
+    if args.clipboard {
+        // Reference the saved file so pasting into a file manager copies it
+        rich_clipboard::set(&image, saved.as_deref().map(Path::new), Some(CLIPBOARD_HOLD))
+            .map_err(CliError::io)?;
+    }
+    Ok(())
+}
+
+fn run_command(command: &CliCommand) -> Result<(), CliError> {
+    match command {
+        CliCommand::Capture(args) => run_capture(args),
+        CliCommand::ListMonitors => {
+            let monitors = screenshot::get_monitors().map_err(CliError::from_capture)?;
+            for (index, m) in monitors.iter().enumerate() {
+                println!(
+                    "{}\t{}\t{}\t{}x{}+{}+{}{}",
+                    index + 1,
+                    m.id,
+                    m.name,
+                    m.width,
+                    m.height,
+                    m.x,
+                    m.y,
+                    if m.is_primary { "\tprimary" } else { "" }
+                );
+            }
+            Ok(())
+        }
+        CliCommand::ListWindows => {
+            let windows = screenshot::get_windows().map_err(CliError::from_capture)?;
+            for w in &windows {
+                println!("{}\t{}\t{}", w.id, w.app_name, w.title);
+            }
+            Ok(())
+        }
+        CliCommand::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Attach to the parent console so output is visible from a terminal
+/// (release builds use the Windows GUI subsystem and have no console)
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Handle a headless invocation, returning the process exit code
+/// Returns `None` when the GUI should start instead
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = match parse(args) {
+        Ok(Some(command)) => command,
+        Ok(None) => return None,
+        Err(e) => {
+            #[cfg(target_os = "windows")]
+            attach_console();
+            eprintln!("{}\n\n{}", e.message, USAGE);
+            return Some(e.code);
+        }
+    };
+
+    #[cfg(target_os = "windows")]
+    attach_console();
+
+    match run_command(&command) {
+        Ok(()) => Some(exit_code::SUCCESS),
+        Err(e) => {
+            eprintln!("Error: {}", e.message);
+            Some(e.code)
+        }
+    }
+}
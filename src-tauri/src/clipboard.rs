@@ -1,11 +1,18 @@
 // Clipboard operations - copy image to system clipboard
 
+use std::path::Path;
+
 use arboard::{Clipboard, ImageData};
 use base64::{engine::general_purpose::STANDARD, Engine};
-use image::GenericImageView;
+use image::RgbaImage;
+use serde::Deserialize;
+use tauri::State;
+
+use crate::capture_store::{self, CaptureKind, CaptureMetadata, CaptureRef, CaptureStore};
+use crate::{image_import, rich_clipboard};
 
 // Maximum allowed image size: 50MB (prevents memory DoS attacks)
-const MAX_IMAGE_SIZE: usize = 50 * 1024 * 1024;
+pub(crate) const MAX_IMAGE_SIZE: usize = 50 * 1024 * 1024;
 
 /// Copy PNG image data to system clipboard
 /// Accepts base64-encoded PNG data (without data URL prefix)
@@ -38,14 +45,16 @@ pub fn copy_image_to_clipboard(base64_data: &str) -> Result<(), String> {
     let img = image::load_from_memory(&png_bytes)
         .map_err(|e| format!("Failed to load image: {}", e))?;
 
-    let (width, height) = img.dimensions();
-    let rgba = img.to_rgba8();
+    set_clipboard_image(&img.to_rgba8())
+}
 
+/// Place RGBA pixels on the system clipboard
+fn set_clipboard_image(image: &RgbaImage) -> Result<(), String> {
     // Create clipboard image data
     let img_data = ImageData {
-        width: width as usize,
-        height: height as usize,
-        bytes: rgba.into_raw().into(),
+        width: image.width() as usize,
+        height: image.height() as usize,
+        bytes: image.as_raw().as_slice().into(),
     };
 
     // Copy to clipboard
@@ -58,3 +67,54 @@ pub fn copy_image_to_clipboard(base64_data: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Copy a stored capture as bitmap, PNG, HTML and a file reference in one go
+/// `file_path` is the saved file to reference; a temporary PNG is used otherwise
+#[tauri::command]
+pub async fn copy_capture_to_clipboard(
+    store: State<'_, CaptureStore>,
+    capture_id: String,
+    file_path: Option<String>,
+) -> Result<(), String> {
+    let image = store.get(&capture_id).ok_or("Capture not found")?;
+    rich_clipboard::set(&image, file_path.as_deref().map(Path::new), None)
+}
+
+/// Arguments of `copy_rgba_to_clipboard` sent alongside the pixels
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CopyRgbaArgs {
+    file_path: Option<String>,
+}
+
+/// Copy raw RGBA pixels (e.g. the rendered editor canvas) in every clipboard format
+/// The pixels are the raw request body; width, height and filePath come from the
+/// x-rgba-args header
+#[tauri::command]
+pub async fn copy_rgba_to_clipboard(request: tauri::ipc::Request<'_>) -> Result<(), String> {
+    let (image, args) = capture_store::rgba_from_request::<CopyRgbaArgs>(&request)?;
+    rich_clipboard::set(&image, args.file_path.as_deref().map(Path::new), None)
+}
+
+/// Paste the clipboard image into the capture store
+/// A copied image file (e.g. from a file manager) is imported when there is no bitmap
+#[tauri::command]
+pub async fn read_image_from_clipboard(store: State<'_, CaptureStore>) -> Result<CaptureRef, String> {
+    let mut clipboard =
+        Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
+
+    let data = match clipboard.get_image() {
+        Ok(data) => data,
+        Err(arboard::Error::ContentNotAvailable) => {
+            let files = clipboard.get().file_list().unwrap_or_default();
+            let file = files.first().ok_or("Clipboard does not contain an image")?;
+            return image_import::import_file(&store, file);
+        }
+        Err(e) => return Err(format!("Failed to read clipboard: {}", e)),
+    };
+
+    image_import::check_decoded_size(data.width as u32, data.height as u32)?;
+    let image = RgbaImage::from_raw(data.width as u32, data.height as u32, data.bytes.into_owned())
+        .ok_or("Clipboard image has invalid dimensions")?;
+    Ok(store.insert(image, CaptureMetadata::new(CaptureKind::Imported, "clipboard")))
+}
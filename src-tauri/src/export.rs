@@ -0,0 +1,350 @@
+// Native export pipeline
+// Encodes captures (or raw RGBA from the editor) in Rust with per-format
+// quality controls instead of relying on what the webview canvas can produce
+
+use std::borrow::Cow;
+use std::io::Cursor;
+use std::path::Path;
+
+use image::codecs::avif::AvifEncoder;
+use image::codecs::bmp::BmpEncoder;
+use image::codecs::ico::IcoEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::codecs::tiff::TiffEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, RgbaImage};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::capture_store::{self, CaptureMetadata, CaptureStore};
+use crate::file_ops;
+use crate::image_metadata::{self, EmbedOptions};
+use crate::png_optimize::{self, OptimizeReport};
+use crate::screenshot::resize_to_fit;
+
+/// Largest icon size ICO supports
+const MAX_ICO_SIZE: u32 = 256;
+
+/// PNG row filter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PngFilter {
+    None,
+    Sub,
+    Up,
+    Avg,
+    Paeth,
+    /// Pick the best filter per row
+    #[default]
+    Adaptive,
+}
+
+impl PngFilter {
+    fn to_filter_type(self) -> FilterType {
+        match self {
+            PngFilter::None => FilterType::NoFilter,
+            PngFilter::Sub => FilterType::Sub,
+            PngFilter::Up => FilterType::Up,
+            PngFilter::Avg => FilterType::Avg,
+            PngFilter::Paeth => FilterType::Paeth,
+            PngFilter::Adaptive => FilterType::Adaptive,
+        }
+    }
+}
+
+fn default_jpeg_quality() -> u8 {
+    90
+}
+
+fn default_avif_quality() -> u8 {
+    80
+}
+
+fn default_avif_speed() -> u8 {
+    6
+}
+
+/// Output format and its encoder settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum ExportSpec {
+    Png {
+        /// Deflate level 0 (store) to 9 (smallest); encoder default when omitted
+        #[serde(default)]
+        compression: Option<u8>,
+        #[serde(default)]
+        filter: PngFilter,
+        /// Run the lossless optimization pass (slower, smaller files)
+        #[serde(default)]
+        optimize: bool,
+    },
+    Jpeg {
+        /// 1-100
+        #[serde(default = "default_jpeg_quality")]
+        quality: u8,
+    },
+    /// Lossless WebP
+    Webp,
+    Avif {
+        /// 1-100
+        #[serde(default = "default_avif_quality")]
+        quality: u8,
+        /// 1 (slowest, smallest) to 10 (fastest)
+        #[serde(default = "default_avif_speed")]
+        speed: u8,
+    },
+    Tiff,
+    Bmp,
+    /// Downscaled to fit 256x256
+    Ico,
+}
+
+impl ExportSpec {
+    /// Default settings for the format implied by a file extension (PNG fallback)
+    pub fn for_path(path: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "jpg" | "jpeg" => ExportSpec::Jpeg {
+                quality: default_jpeg_quality(),
+            },
+            "webp" => ExportSpec::Webp,
+            "avif" => ExportSpec::Avif {
+                quality: default_avif_quality(),
+                speed: default_avif_speed(),
+            },
+            "tif" | "tiff" => ExportSpec::Tiff,
+            "bmp" => ExportSpec::Bmp,
+            "ico" => ExportSpec::Ico,
+            _ => ExportSpec::Png {
+                compression: None,
+                filter: PngFilter::default(),
+                optimize: false,
+            },
+        }
+    }
+
+    /// Conventional file extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportSpec::Png { .. } => "png",
+            ExportSpec::Jpeg { .. } => "jpg",
+            ExportSpec::Webp => "webp",
+            ExportSpec::Avif { .. } => "avif",
+            ExportSpec::Tiff => "tiff",
+            ExportSpec::Bmp => "bmp",
+            ExportSpec::Ico => "ico",
+        }
+    }
+}
+
+/// Where an export was written and how large it is
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub byte_size: usize,
+    /// Savings from the PNG optimization pass, when it ran
+    pub optimization: Option<OptimizeReport>,
+}
+
+fn encode_error(format: &str, e: impl std::fmt::Display) -> String {
+    format!("Failed to encode {}: {}", format, e)
+}
+
+/// Encode an image according to the export spec
+pub fn encode(image: &RgbaImage, spec: &ExportSpec) -> Result<Vec<u8>, String> {
+    let (width, height) = image.dimensions();
+    let mut bytes = Cursor::new(Vec::new());
+
+    match spec {
+        ExportSpec::Png { compression, filter, .. } => {
+            let compression = match compression {
+                None => CompressionType::Default,
+                Some(0) => CompressionType::Uncompressed,
+                Some(level) => CompressionType::Level((*level).min(9)),
+            };
+            PngEncoder::new_with_quality(&mut bytes, compression, filter.to_filter_type())
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("PNG", e))?;
+        }
+        ExportSpec::Jpeg { quality } => {
+            // JPEG has no alpha channel
+            let rgb = DynamicImage::ImageRgba8(image.clone()).to_rgb8();
+            JpegEncoder::new_with_quality(&mut bytes, (*quality).clamp(1, 100))
+                .write_image(rgb.as_raw(), width, height, ExtendedColorType::Rgb8)
+                .map_err(|e| encode_error("JPEG", e))?;
+        }
+        ExportSpec::Webp => {
+            WebPEncoder::new_lossless(&mut bytes)
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("WebP", e))?;
+        }
+        ExportSpec::Avif { quality, speed } => {
+            AvifEncoder::new_with_speed_quality(&mut bytes, (*speed).clamp(1, 10), (*quality).clamp(1, 100))
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("AVIF", e))?;
+        }
+        ExportSpec::Tiff => {
+            TiffEncoder::new(&mut bytes)
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("TIFF", e))?;
+        }
+        ExportSpec::Bmp => {
+            BmpEncoder::new(&mut bytes)
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("BMP", e))?;
+        }
+        ExportSpec::Ico => {
+            let icon = if width > MAX_ICO_SIZE || height > MAX_ICO_SIZE {
+                resize_to_fit(image, MAX_ICO_SIZE)
+            } else {
+                image.clone()
+            };
+            IcoEncoder::new(&mut bytes)
+                .write_image(icon.as_raw(), icon.width(), icon.height(), ExtendedColorType::Rgba8)
+                .map_err(|e| encode_error("ICO", e))?;
+        }
+    }
+
+    Ok(bytes.into_inner())
+}
+
+/// Largest file an export may write: the usual cap, raised for large images since
+/// TIFF, BMP and uncompressed PNG store 4 bytes per pixel plus headers
+pub(crate) fn max_file_size(image: &RgbaImage) -> usize {
+    let raw = image.width() as usize * image.height() as usize * 4;
+    file_ops::MAX_FILE_SIZE.max(raw + raw / 8 + 1024 * 1024)
+}
+
+/// Encode with the spec, then keep the optimized PNG if it is smaller
+fn encode_optimized(image: &RgbaImage, spec: &ExportSpec) -> Result<(Vec<u8>, OptimizeReport), String> {
+    let baseline = encode(image, spec)?;
+    let (optimized, encoding) = png_optimize::optimize(image)?;
+    if optimized.len() < baseline.len() {
+        let report = OptimizeReport::new(baseline.len(), optimized.len(), encoding);
+        Ok((optimized, report))
+    } else {
+        let report = OptimizeReport::new(baseline.len(), baseline.len(), "unchanged".to_string());
+        Ok((baseline, report))
+    }
+}
+
+/// Format-independent export settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Capture the pixels came from; supplies scale factor and provenance for raw exports
+    pub capture_id: Option<String>,
+    /// Display scale the pixels were captured at (the capture's, or 1.0, when omitted)
+    pub scale_factor: Option<f32>,
+    /// Downscale HiDPI pixels to logical (1x) size
+    pub logical_size: bool,
+    /// Embed capture provenance (PNG, JPEG and WebP)
+    pub embed: Option<EmbedOptions>,
+}
+
+/// Downscale to logical resolution with a high-quality filter
+fn to_logical_size(image: &RgbaImage, scale_factor: f32) -> RgbaImage {
+    let width = ((image.width() as f32 / scale_factor).round() as u32).max(1);
+    let height = ((image.height() as f32 / scale_factor).round() as u32).max(1);
+    image::imageops::resize(image, width, height, image::imageops::FilterType::Lanczos3)
+}
+
+/// Encode and write an image, validating the destination like `save_file`
+/// Density and provenance are added after optimization so the optimizer cannot strip them
+pub fn export(
+    image: &RgbaImage,
+    spec: &ExportSpec,
+    path: &str,
+    options: &ExportOptions,
+    metadata: Option<&CaptureMetadata>,
+) -> Result<ExportResult, String> {
+    let mut scale_factor = options
+        .scale_factor
+        .or(metadata.map(|m| m.scale_factor))
+        .filter(|s| *s > 0.0)
+        .unwrap_or(1.0);
+    let image = if options.logical_size && scale_factor > 1.0 {
+        let logical = to_logical_size(image, scale_factor);
+        scale_factor = 1.0;
+        Cow::Owned(logical)
+    } else {
+        Cow::Borrowed(image)
+    };
+
+    let (mut bytes, optimization) = match spec {
+        ExportSpec::Png { optimize: true, .. } => {
+            let (bytes, report) = encode_optimized(&image, spec)?;
+            (bytes, Some(report))
+        }
+        _ => (encode(&image, spec)?, None),
+    };
+    bytes = image_metadata::set_density(bytes, spec, image_metadata::dpi_for_scale(scale_factor))?;
+    if let Some(embed) = &options.embed {
+        bytes = image_metadata::embed(bytes, spec, &embed.provenance(metadata))?;
+    }
+    let path = file_ops::write_file_with_limit(path, &bytes, max_file_size(&image))?;
+    Ok(ExportResult {
+        path,
+        byte_size: bytes.len(),
+        optimization,
+    })
+}
+
+/// Export a stored capture
+#[tauri::command]
+pub async fn export_capture(
+    store: State<'_, CaptureStore>,
+    capture_id: String,
+    spec: ExportSpec,
+    path: String,
+    options: Option<ExportOptions>,
+) -> Result<ExportResult, String> {
+    let image = store.get(&capture_id).ok_or("Capture not found")?;
+    let metadata = store.metadata(&capture_id);
+    export(&image, &spec, &path, &options.unwrap_or_default(), metadata.as_ref())
+}
+
+/// Arguments of `export_image` sent alongside the pixels
+#[derive(Deserialize)]
+struct ExportImageArgs {
+    spec: ExportSpec,
+    path: String,
+    options: Option<ExportOptions>,
+}
+
+/// Export raw RGBA pixels (e.g. the rendered editor canvas)
+/// The pixels are the raw request body; width, height, spec, path and options come
+/// from the x-rgba-args header. Set `options.capture_id` to carry over the scale
+/// factor and provenance of the original capture
+#[tauri::command]
+pub async fn export_image(
+    store: State<'_, CaptureStore>,
+    request: tauri::ipc::Request<'_>,
+) -> Result<ExportResult, String> {
+    let (image, args) = capture_store::rgba_from_request::<ExportImageArgs>(&request)?;
+    let ExportImageArgs { spec, path, options } = args;
+    let options = options.unwrap_or_default();
+    let metadata = options.capture_id.as_deref().and_then(|id| store.metadata(id));
+    export(&image, &spec, &path, &options, metadata.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_limit_fits_uncompressed_formats() {
+        // 5K capture: ~59 MB as BMP, above the default cap
+        let image = RgbaImage::new(5120, 2880);
+        for spec in [ExportSpec::Bmp, ExportSpec::Tiff] {
+            let bytes = encode(&image, &spec).unwrap();
+            assert!(bytes.len() > file_ops::MAX_FILE_SIZE);
+            assert!(bytes.len() <= max_file_size(&image));
+        }
+        assert_eq!(max_file_size(&RgbaImage::new(640, 480)), file_ops::MAX_FILE_SIZE);
+    }
+}
@@ -3,22 +3,32 @@
 use std::path::PathBuf;
 
 /// Maximum file size limit (50MB) - prevents DoS from excessively large exports
-const MAX_FILE_SIZE: usize = 50 * 1024 * 1024;
+pub(crate) const MAX_FILE_SIZE: usize = 50 * 1024 * 1024;
 
 /// Save binary data to file at specified path
 /// Security: Validates path and enforces size limits
 #[tauri::command]
 pub async fn save_file(path: String, data: Vec<u8>) -> Result<String, String> {
+    write_file(&path, &data)
+}
+
+/// Validate the destination path and write data, returning the canonical path
+pub(crate) fn write_file(path: &str, data: &[u8]) -> Result<String, String> {
+    write_file_with_limit(path, data, MAX_FILE_SIZE)
+}
+
+/// Like `write_file`, with a caller-chosen size limit (e.g. for recordings)
+pub(crate) fn write_file_with_limit(path: &str, data: &[u8], max_size: usize) -> Result<String, String> {
     // Enforce file size limit
-    if data.len() > MAX_FILE_SIZE {
+    if data.len() > max_size {
         return Err(format!(
             "File size ({} MB) exceeds maximum allowed ({} MB)",
             data.len() / (1024 * 1024),
-            MAX_FILE_SIZE / (1024 * 1024)
+            max_size / (1024 * 1024)
         ));
     }
 
-    let path = PathBuf::from(&path);
+    let path = PathBuf::from(path);
 
     // Canonicalize path to prevent directory traversal attacks
     // For new files, canonicalize the parent directory
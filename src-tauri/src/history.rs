@@ -0,0 +1,397 @@
+// Capture history - persistent on-disk library of past captures
+// Each entry is a folder in the app-data library holding the original PNG,
+// a thumbnail and a metadata JSON, pruned by a configurable retention policy
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use tauri::{ipc, AppHandle, Manager, State};
+
+use crate::capture_store::{now_millis, CaptureMetadata, CaptureRef, CaptureStore};
+use crate::screenshot::{image_to_png_bytes, resize_to_fit};
+
+const ORIGINAL_FILE: &str = "original.png";
+const THUMBNAIL_FILE: &str = "thumbnail.png";
+const METADATA_FILE: &str = "metadata.json";
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Longest side of history thumbnails in pixels
+const THUMBNAIL_SIZE: u32 = 320;
+
+/// Limits applied to unpinned entries after every write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    pub max_count: Option<usize>,
+    pub max_bytes: Option<u64>,
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_count: Some(500),
+            max_bytes: Some(2 * 1024 * 1024 * 1024),
+            max_age_days: None,
+        }
+    }
+}
+
+/// User-configurable history behavior, persisted in the library folder
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistorySettings {
+    /// Record every capture automatically instead of only on request
+    pub auto_record: bool,
+    pub retention: RetentionPolicy,
+}
+
+/// A capture saved in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    /// Unix timestamp in milliseconds when the entry was written
+    pub created_at: u64,
+    pub width: u32,
+    pub height: u32,
+    /// Size of the original and thumbnail on disk
+    pub byte_size: u64,
+    pub pinned: bool,
+    pub metadata: CaptureMetadata,
+}
+
+/// Filters for listing history entries; all fields are optional
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryQuery {
+    /// Only entries created at or after this Unix millisecond timestamp
+    pub from: Option<u64>,
+    /// Only entries created at or before this Unix millisecond timestamp
+    pub to: Option<u64>,
+    /// Case-insensitive substring of the source application name
+    pub app_name: Option<String>,
+    /// Case-insensitive substring of the source window title
+    pub window_title: Option<String>,
+    #[serde(default)]
+    pub pinned_only: bool,
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        let contains = |value: &Option<String>, needle: &str| {
+            value
+                .as_deref()
+                .is_some_and(|v| v.to_lowercase().contains(&needle.to_lowercase()))
+        };
+
+        self.from.is_none_or(|from| entry.created_at >= from)
+            && self.to.is_none_or(|to| entry.created_at <= to)
+            && self
+                .app_name
+                .as_deref()
+                .is_none_or(|needle| contains(&entry.metadata.app_name, needle))
+            && self
+                .window_title
+                .as_deref()
+                .is_none_or(|needle| contains(&entry.metadata.window_title, needle))
+            && (!self.pinned_only || entry.pinned)
+    }
+}
+
+/// On-disk capture library, managed as Tauri state
+pub struct HistoryStore {
+    dir: PathBuf,
+    next_id: AtomicU64,
+    settings: Mutex<HistorySettings>,
+    /// In-memory index of entries, oldest first
+    entries: Mutex<Vec<HistoryEntry>>,
+}
+
+impl HistoryStore {
+    /// Open (or create) the library folder and index existing entries
+    pub fn open(dir: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history folder: {}", e))?;
+
+        let settings = fs::read(dir.join(SETTINGS_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        let mut entries: Vec<HistoryEntry> = fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read history folder: {}", e))?
+            .filter_map(|item| item.ok())
+            .filter(|item| item.path().is_dir())
+            .filter_map(|item| fs::read(item.path().join(METADATA_FILE)).ok())
+            .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+            .collect();
+        entries.sort_by_key(|e| e.created_at);
+
+        Ok(Self {
+            dir,
+            next_id: AtomicU64::new(0),
+            settings: Mutex::new(settings),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, Vec<HistoryEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn settings(&self) -> HistorySettings {
+        self.settings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Replace and persist settings, then apply the new retention policy
+    pub fn set_settings(&self, settings: HistorySettings) -> Result<(), String> {
+        let json = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+        fs::write(self.dir.join(SETTINGS_FILE), json)
+            .map_err(|e| format!("Failed to save history settings: {}", e))?;
+        *self
+            .settings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = settings;
+        self.apply_retention();
+        Ok(())
+    }
+
+    /// Entry folder for an id, rejecting ids that could escape the library
+    fn entry_dir(&self, id: &str) -> Result<PathBuf, String> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err("Invalid history id".to_string());
+        }
+        Ok(self.dir.join(id))
+    }
+
+    /// Write a capture (original, thumbnail, metadata) into the library
+    pub fn add(&self, image: &RgbaImage, metadata: CaptureMetadata) -> Result<HistoryEntry, String> {
+        let created_at = now_millis();
+        let id = format!(
+            "{}-{}",
+            created_at,
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let dir = self.entry_dir(&id)?;
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history entry: {}", e))?;
+
+        let original = image_to_png_bytes(image)?;
+        let thumbnail = image_to_png_bytes(&resize_to_fit(image, THUMBNAIL_SIZE))?;
+        let entry = HistoryEntry {
+            id,
+            created_at,
+            width: image.width(),
+            height: image.height(),
+            byte_size: (original.len() + thumbnail.len()) as u64,
+            pinned: false,
+            metadata,
+        };
+
+        let write = || -> Result<(), String> {
+            fs::write(dir.join(ORIGINAL_FILE), &original).map_err(|e| e.to_string())?;
+            fs::write(dir.join(THUMBNAIL_FILE), &thumbnail).map_err(|e| e.to_string())?;
+            // Metadata last: an entry only counts once its metadata exists
+            write_metadata(&dir, &entry)
+        };
+        if let Err(e) = write() {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(format!("Failed to write history entry: {}", e));
+        }
+
+        self.lock_entries().push(entry.clone());
+        self.apply_retention();
+        Ok(entry)
+    }
+
+    /// Entries matching the query, newest first
+    pub fn list(&self, query: &HistoryQuery) -> Vec<HistoryEntry> {
+        let entries = self.lock_entries();
+        let matching = entries.iter().rev().filter(|e| query.matches(e)).cloned();
+        match query.limit {
+            Some(limit) => matching.take(limit).collect(),
+            None => matching.collect(),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<HistoryEntry> {
+        self.lock_entries().iter().find(|e| e.id == id).cloned()
+    }
+
+    /// Decode the original image of an entry
+    pub fn load_original(&self, id: &str) -> Result<RgbaImage, String> {
+        let bytes = fs::read(self.entry_dir(id)?.join(ORIGINAL_FILE))
+            .map_err(|e| format!("Failed to read history entry: {}", e))?;
+        image::load_from_memory(&bytes)
+            .map(|img| img.to_rgba8())
+            .map_err(|e| format!("Failed to decode history entry: {}", e))
+    }
+
+    /// Read the thumbnail PNG of an entry
+    pub fn load_thumbnail(&self, id: &str) -> Result<Vec<u8>, String> {
+        fs::read(self.entry_dir(id)?.join(THUMBNAIL_FILE))
+            .map_err(|e| format!("Failed to read history thumbnail: {}", e))
+    }
+
+    pub fn set_pinned(&self, id: &str, pinned: bool) -> Result<HistoryEntry, String> {
+        let dir = self.entry_dir(id)?;
+        let mut entries = self.lock_entries();
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or("History entry not found")?;
+        entry.pinned = pinned;
+        write_metadata(&dir, entry)?;
+        Ok(entry.clone())
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let dir = self.entry_dir(id)?;
+        let mut entries = self.lock_entries();
+        let index = entries
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or("History entry not found")?;
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete history entry: {}", e))?;
+        entries.remove(index);
+        Ok(())
+    }
+
+    /// Remove the oldest unpinned entries that exceed the retention policy
+    fn apply_retention(&self) {
+        let policy = self.settings().retention;
+        let mut entries = self.lock_entries();
+
+        let min_created_at = policy
+            .max_age_days
+            .map(|days| now_millis().saturating_sub(days as u64 * 24 * 60 * 60 * 1000));
+        let mut count = entries.len();
+        let mut total_bytes: u64 = entries.iter().map(|e| e.byte_size).sum();
+
+        // Entries are oldest first, so the first unpinned ones go first
+        let mut expired = Vec::new();
+        for entry in entries.iter().filter(|e| !e.pinned) {
+            let too_old = min_created_at.is_some_and(|min| entry.created_at < min);
+            let too_many = policy.max_count.is_some_and(|max| count > max);
+            let too_big = policy.max_bytes.is_some_and(|max| total_bytes > max);
+            if !(too_old || too_many || too_big) {
+                continue;
+            }
+            count -= 1;
+            total_bytes -= entry.byte_size;
+            expired.push(entry.id.clone());
+        }
+
+        for id in &expired {
+            if let Ok(dir) = self.entry_dir(id) {
+                if let Err(e) = fs::remove_dir_all(&dir) {
+                    println!("Failed to prune history entry {}: {}", id, e);
+                }
+            }
+        }
+        entries.retain(|e| !expired.contains(&e.id));
+    }
+}
+
+fn write_metadata(dir: &Path, entry: &HistoryEntry) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(entry).map_err(|e| e.to_string())?;
+    fs::write(dir.join(METADATA_FILE), json).map_err(|e| e.to_string())
+}
+
+/// The history library, or an error when it failed to open at startup
+fn history_store(app: &AppHandle) -> Result<State<'_, HistoryStore>, String> {
+    app.try_state::<HistoryStore>()
+        .ok_or_else(|| "Capture history is unavailable".to_string())
+}
+
+/// Record a stored capture in the background when auto-record is enabled
+/// Keeps PNG encoding and disk writes off the capture command's critical path
+pub fn auto_record(app: &AppHandle, capture_ref: &CaptureRef) {
+    // No library when it failed to open at startup
+    let Some(history) = app.try_state::<HistoryStore>() else {
+        return;
+    };
+    if !history.settings().auto_record {
+        return;
+    }
+
+    let store = app.state::<CaptureStore>();
+    let (Some(image), Some(metadata)) = (store.get(&capture_ref.id), store.metadata(&capture_ref.id))
+    else {
+        return;
+    };
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = app.state::<HistoryStore>().add(&image, metadata) {
+            println!("Failed to record capture history: {}", e);
+        }
+    });
+}
+
+/// Save a stored capture into the history library
+#[tauri::command]
+pub async fn add_to_history(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    capture_id: String,
+) -> Result<HistoryEntry, String> {
+    let history = history_store(&app)?;
+    let image = store.get(&capture_id).ok_or("Capture not found")?;
+    let metadata = store.metadata(&capture_id).ok_or("Capture not found")?;
+    history.add(&image, metadata)
+}
+
+/// List history entries, newest first
+#[tauri::command]
+pub fn list_history(app: AppHandle, query: Option<HistoryQuery>) -> Result<Vec<HistoryEntry>, String> {
+    Ok(history_store(&app)?.list(&query.unwrap_or_default()))
+}
+
+/// Get the PNG thumbnail of a history entry
+#[tauri::command]
+pub fn get_history_thumbnail(app: AppHandle, id: String) -> Result<ipc::Response, String> {
+    Ok(ipc::Response::new(history_store(&app)?.load_thumbnail(&id)?))
+}
+
+/// Load a history entry back into the capture store so the editor can open it
+#[tauri::command]
+pub async fn reopen_history_entry(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    id: String,
+) -> Result<CaptureRef, String> {
+    let history = history_store(&app)?;
+    let entry = history.get(&id).ok_or("History entry not found")?;
+    let image = history.load_original(&id)?;
+    Ok(store.insert(image, entry.metadata))
+}
+
+/// Pin or unpin a history entry (pinned entries are never pruned)
+#[tauri::command]
+pub fn set_history_pinned(app: AppHandle, id: String, pinned: bool) -> Result<HistoryEntry, String> {
+    history_store(&app)?.set_pinned(&id, pinned)
+}
+
+/// Delete a history entry and its files
+#[tauri::command]
+pub fn delete_history_entry(app: AppHandle, id: String) -> Result<(), String> {
+    history_store(&app)?.delete(&id)
+}
+
+/// Get current history settings
+#[tauri::command]
+pub fn get_history_settings(app: AppHandle) -> Result<HistorySettings, String> {
+    Ok(history_store(&app)?.settings())
+}
+
+/// Update history settings and prune entries under the new retention policy
+#[tauri::command]
+pub fn update_history_settings(app: AppHandle, settings: HistorySettings) -> Result<(), String> {
+    history_store(&app)?.set_settings(settings)
+}
@@ -0,0 +1,117 @@
+// Image import
+// Brings existing images (files, clipboard contents) into the capture store so
+// they can be edited and beautified like fresh screenshots
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use image::{ImageError, ImageFormat, ImageReader, Limits, RgbaImage};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+use crate::capture_store::{CaptureKind, CaptureMetadata, CaptureRef, CaptureStore};
+use crate::clipboard::MAX_IMAGE_SIZE;
+
+/// Largest decoded RGBA buffer accepted (256MB, about 8K x 8K)
+pub(crate) const MAX_DECODED_SIZE: usize = 256 * 1024 * 1024;
+
+/// Formats accepted for import; GIFs contribute their first frame
+const SUPPORTED_FORMATS: [ImageFormat; 5] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::WebP,
+    ImageFormat::Bmp,
+    ImageFormat::Gif,
+];
+
+/// Check a decoded image against the memory cap
+pub(crate) fn check_decoded_size(width: u32, height: u32) -> Result<(), String> {
+    let size = width as usize * height as usize * 4;
+    if size > MAX_DECODED_SIZE {
+        return Err(format!(
+            "Image too large: {}x{} (max {} MB decoded)",
+            width,
+            height,
+            MAX_DECODED_SIZE / (1024 * 1024)
+        ));
+    }
+    Ok(())
+}
+
+/// Decode an image file, sniffing the format from its magic bytes
+/// The extension is ignored so renamed or extensionless files still open
+pub(crate) fn decode_file(path: &Path) -> Result<RgbaImage, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_IMAGE_SIZE as u64 {
+        return Err(format!(
+            "Image too large: {} bytes (max {} bytes)",
+            size, MAX_IMAGE_SIZE
+        ));
+    }
+
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = ImageReader::new(BufReader::new(file))
+        .with_guessed_format()
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    match reader.format() {
+        Some(format) if SUPPORTED_FORMATS.contains(&format) => {}
+        _ => return Err("Unsupported image format (expected PNG, JPEG, WebP, BMP or GIF)".to_string()),
+    }
+
+    // Refuse oversized images before allocating pixel buffers
+    let mut limits = Limits::default();
+    limits.max_alloc = Some(MAX_DECODED_SIZE as u64);
+    reader.limits(limits);
+    let image = reader.decode().map_err(|e| match e {
+        ImageError::Limits(_) => format!(
+            "Image too large (max {} MB decoded)",
+            MAX_DECODED_SIZE / (1024 * 1024)
+        ),
+        e => format!("Failed to decode {}: {}", path.display(), e),
+    })?;
+    check_decoded_size(image.width(), image.height())?;
+    Ok(image.to_rgba8())
+}
+
+/// Decode an image file and register it in the capture store
+/// The file name is kept as the title so naming templates can use it
+pub(crate) fn import_file(store: &CaptureStore, path: &Path) -> Result<CaptureRef, String> {
+    let image = decode_file(path)?;
+    let mut metadata = CaptureMetadata::new(CaptureKind::Imported, "file");
+    metadata.window_title = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+    Ok(store.insert(image, metadata))
+}
+
+/// Open an existing image (PNG, JPEG, WebP, BMP or GIF first frame) for editing
+#[tauri::command]
+pub async fn open_image_file(store: State<'_, CaptureStore>, path: String) -> Result<CaptureRef, String> {
+    import_file(&store, Path::new(&path))
+}
+
+/// Import files handed to the app (launch args, file association, drag-and-drop)
+/// and show the first one that decodes in the editor
+/// Emits "image-opened" (CaptureRef) or "image-open-failed" (error string) to the main window
+pub fn open_in_editor<R: Runtime>(app: &AppHandle<R>, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    // Decoding large images must not block the event loop
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = app.state::<CaptureStore>();
+        let mut error = "No image to open".to_string();
+        for path in &paths {
+            match import_file(&store, path) {
+                Ok(capture) => {
+                    println!("Opened image: {}", path.display());
+                    let _ = app.emit_to("main", "image-opened", capture);
+                    return;
+                }
+                Err(e) => {
+                    println!("Failed to open image: {}", e);
+                    error = e;
+                }
+            }
+        }
+        let _ = app.emit_to("main", "image-open-failed", error);
+    });
+}
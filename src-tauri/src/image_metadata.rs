@@ -0,0 +1,824 @@
+// Metadata embedded in exported images
+// PNG gets tEXt/iTXt chunks; JPEG and WebP get an EXIF block with the standard
+// fields plus an XMP packet carrying everything. Physical resolution goes into
+// PNG pHYs and the JPEG JFIF header. Metadata is spliced into the already-encoded
+// bytes, so the encoders and the PNG optimizer stay untouched.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::{DateTime, Local, SecondsFormat, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::capture_store::{now_millis, CaptureMetadata};
+use crate::export::ExportSpec;
+
+/// Largest file read back for metadata (matches file_ops limit)
+const MAX_INPUT_SIZE: u64 = 50 * 1024 * 1024;
+
+const SOFTWARE_NAME: &str = "beautiFULLshot";
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_NAMESPACE: &str = "https://github.com/itsddvn/beautiFULLshot/ns/capture/1.0/";
+
+const KEY_CREATION_TIME: &str = "Creation Time";
+const KEY_SOFTWARE: &str = "Software";
+const KEY_DESCRIPTION: &str = "Description";
+const KEY_APP: &str = "Source Application";
+const KEY_WINDOW: &str = "Window Title";
+const KEY_MONITOR: &str = "Monitor";
+const KEY_SCALE: &str = "Scale Factor";
+
+/// PNG text keyword and XMP property for each field
+const FIELDS: [(&str, &str); 7] = [
+    (KEY_CREATION_TIME, "xmp:CreateDate"),
+    (KEY_SOFTWARE, "xmp:CreatorTool"),
+    (KEY_DESCRIPTION, "dc:description"),
+    (KEY_APP, "bfs:SourceApplication"),
+    (KEY_WINDOW, "bfs:WindowTitle"),
+    (KEY_MONITOR, "bfs:Monitor"),
+    (KEY_SCALE, "bfs:ScaleFactor"),
+];
+
+/// Where an image came from, as embedded in (or read back from) a file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageProvenance {
+    /// RFC 3339 local time
+    pub captured_at: Option<String>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub monitor_name: Option<String>,
+    pub scale_factor: Option<f32>,
+    /// beautiFULLshot version that wrote the file
+    pub app_version: Option<String>,
+    pub description: Option<String>,
+}
+
+fn format_millis(millis: u64) -> Option<String> {
+    Local
+        .timestamp_millis_opt(millis as i64)
+        .single()
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, false))
+}
+
+impl ImageProvenance {
+    pub fn from_capture(metadata: &CaptureMetadata, description: Option<String>) -> Self {
+        Self {
+            captured_at: format_millis(metadata.captured_at),
+            app_name: metadata.app_name.clone(),
+            window_title: metadata.window_title.clone(),
+            monitor_name: metadata.monitor_name.clone(),
+            scale_factor: Some(metadata.scale_factor),
+            app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            description: description.filter(|d| !d.trim().is_empty()),
+        }
+    }
+
+    /// Provenance for pixels that have no stored capture behind them
+    pub fn now(description: Option<String>) -> Self {
+        Self {
+            captured_at: format_millis(now_millis()),
+            app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            description: description.filter(|d| !d.trim().is_empty()),
+            ..Self::default()
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        match key {
+            KEY_CREATION_TIME => self.captured_at.clone(),
+            KEY_SOFTWARE => self.app_version.as_ref().map(|v| format!("{} {}", SOFTWARE_NAME, v)),
+            KEY_DESCRIPTION => self.description.clone(),
+            KEY_APP => self.app_name.clone(),
+            KEY_WINDOW => self.window_title.clone(),
+            KEY_MONITOR => self.monitor_name.clone(),
+            KEY_SCALE => self.scale_factor.map(|s| s.to_string()),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: String) {
+        match key {
+            KEY_CREATION_TIME => self.captured_at = Some(value),
+            KEY_SOFTWARE => {
+                // Files written by other tools keep their full Software string
+                let version = value.strip_prefix(SOFTWARE_NAME).map(|v| v.trim().to_string());
+                self.app_version = version.or(Some(value));
+            }
+            KEY_DESCRIPTION => self.description = Some(value),
+            KEY_APP => self.app_name = Some(value),
+            KEY_WINDOW => self.window_title = Some(value),
+            KEY_MONITOR => self.monitor_name = Some(value),
+            KEY_SCALE => self.scale_factor = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+
+    /// Present fields as (PNG keyword, value); NULs are dropped since every
+    /// container uses them as terminators
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        FIELDS
+            .iter()
+            .filter_map(|(key, _)| self.get(key).map(|v| (*key, v.replace('\0', ""))))
+            .filter(|(_, v)| !v.is_empty())
+            .collect()
+    }
+
+    /// EXIF DateTime ("YYYY:MM:DD HH:MM:SS")
+    fn exif_date_time(&self) -> Option<String> {
+        let captured_at = self.captured_at.as_deref()?;
+        let time = DateTime::parse_from_rfc3339(captured_at).ok()?;
+        Some(time.format("%Y:%m:%d %H:%M:%S").to_string())
+    }
+}
+
+/// Export option asking for provenance to be embedded
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EmbedOptions {
+    /// User-supplied description
+    pub description: Option<String>,
+}
+
+impl EmbedOptions {
+    /// Build provenance from the source capture, or just a timestamp when there is none
+    pub fn provenance(&self, metadata: Option<&CaptureMetadata>) -> ImageProvenance {
+        match metadata {
+            Some(metadata) => ImageProvenance::from_capture(metadata, self.description.clone()),
+            None => ImageProvenance::now(self.description.clone()),
+        }
+    }
+}
+
+// --- PNG ---
+
+fn png_chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(kind);
+    hasher.update(data);
+
+    let mut chunk = Vec::with_capacity(data.len() + 12);
+    chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(kind);
+    chunk.extend_from_slice(data);
+    chunk.extend_from_slice(&hasher.finalize().to_be_bytes());
+    chunk
+}
+
+/// tEXt for ASCII values, uncompressed iTXt (UTF-8) otherwise
+fn png_text_chunk(keyword: &str, value: &str) -> Vec<u8> {
+    let mut data = keyword.as_bytes().to_vec();
+    data.push(0);
+    if value.is_ascii() {
+        data.extend_from_slice(value.as_bytes());
+        png_chunk(b"tEXt", &data)
+    } else {
+        // Compression flag, method, empty language tag and translated keyword
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(value.as_bytes());
+        png_chunk(b"iTXt", &data)
+    }
+}
+
+/// Iterate (type, data) over PNG chunks, stopping at IEND or truncated data
+fn png_chunks(bytes: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut pos = PNG_SIGNATURE.len();
+    std::iter::from_fn(move || {
+        let header = bytes.get(pos..pos + 8)?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        let data = bytes.get(pos + 8..pos + 8 + len)?;
+        if kind == b"IEND" {
+            return None;
+        }
+        pos += len + 12;
+        Some((kind, data))
+    })
+}
+
+/// Insert chunks right after IHDR, which must come first
+fn insert_png_chunks(bytes: Vec<u8>, chunks: Vec<u8>) -> Result<Vec<u8>, String> {
+    // Signature plus the 25-byte IHDR chunk
+    const IHDR_END: usize = 8 + 25;
+    if bytes.len() < IHDR_END || !bytes.starts_with(PNG_SIGNATURE) || &bytes[12..16] != b"IHDR" {
+        return Err("Not a PNG file".to_string());
+    }
+
+    let mut out = Vec::with_capacity(bytes.len() + chunks.len());
+    out.extend_from_slice(&bytes[..IHDR_END]);
+    out.extend(chunks);
+    out.extend_from_slice(&bytes[IHDR_END..]);
+    Ok(out)
+}
+
+fn embed_png(bytes: Vec<u8>, provenance: &ImageProvenance) -> Result<Vec<u8>, String> {
+    let chunks = provenance
+        .fields()
+        .into_iter()
+        .flat_map(|(keyword, value)| png_text_chunk(keyword, &value))
+        .collect();
+    insert_png_chunks(bytes, chunks)
+}
+
+fn read_png(bytes: &[u8]) -> ImageProvenance {
+    let mut provenance = ImageProvenance::default();
+    for (kind, data) in png_chunks(bytes) {
+        let Some(split) = data.iter().position(|&b| b == 0) else {
+            continue;
+        };
+        let keyword = String::from_utf8_lossy(&data[..split]);
+        let rest = &data[split + 1..];
+        let value = match kind {
+            // Latin-1 maps directly onto the first 256 code points
+            b"tEXt" => rest.iter().map(|&b| b as char).collect(),
+            // Compressed iTXt is never written by us, so it is skipped
+            b"iTXt" if rest.len() >= 2 && rest[0] == 0 => {
+                let mut parts = rest[2..].splitn(3, |&b| b == 0);
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(_), Some(_), Some(text)) => String::from_utf8_lossy(text).into_owned(),
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        provenance.set(&keyword, value);
+    }
+    provenance
+}
+
+// --- EXIF / XMP ---
+
+/// Minimal little-endian TIFF with IFD0 ImageDescription, Software and DateTime
+/// Only ASCII values are written, as EXIF requires; XMP carries the rest
+fn exif_block(provenance: &ImageProvenance) -> Option<Vec<u8>> {
+    let ascii = |value: Option<String>| value.filter(|v| v.is_ascii() && !v.is_empty());
+    // Entries must be sorted by tag
+    let entries: Vec<(u16, Vec<u8>)> = [
+        (0x010e, ascii(provenance.get(KEY_DESCRIPTION))),
+        (0x0131, ascii(provenance.get(KEY_SOFTWARE))),
+        (0x0132, provenance.exif_date_time()),
+    ]
+    .into_iter()
+    .filter_map(|(tag, value)| {
+        let mut value = value?.replace('\0', "").into_bytes();
+        value.push(0);
+        Some((tag, value))
+    })
+    .collect();
+    if entries.is_empty() {
+        return None;
+    }
+
+    let ifd_size = 2 + entries.len() * 12 + 4;
+    let data_start = 8 + ifd_size;
+    let mut tiff = b"II*\0".to_vec();
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+
+    let mut data = Vec::new();
+    for (tag, value) in &entries {
+        tiff.extend_from_slice(&tag.to_le_bytes());
+        // Type 2 = ASCII
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        if value.len() <= 4 {
+            let mut inline = [0u8; 4];
+            inline[..value.len()].copy_from_slice(value);
+            tiff.extend_from_slice(&inline);
+        } else {
+            tiff.extend_from_slice(&((data_start + data.len()) as u32).to_le_bytes());
+            data.extend_from_slice(value);
+            // Values start on word boundaries
+            if data.len() % 2 == 1 {
+                data.push(0);
+            }
+        }
+    }
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend(data);
+    Some(tiff)
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn xmp_packet(provenance: &ImageProvenance) -> String {
+    let mut properties = String::new();
+    for (key, value) in provenance.fields() {
+        let Some((_, property)) = FIELDS.iter().find(|(k, _)| *k == key) else {
+            continue;
+        };
+        let value = escape_xml(&value);
+        if key == KEY_DESCRIPTION {
+            let _ = write!(
+                properties,
+                "<dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:description>",
+                value
+            );
+        } else {
+            let _ = write!(properties, "<{0}>{1}</{0}>", property, value);
+        }
+    }
+
+    format!(
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">",
+            "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">",
+            "<rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"",
+            " xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:bfs=\"{}\">",
+            "{}",
+            "</rdf:Description></rdf:RDF></x:xmpmeta>",
+            "<?xpacket end=\"w\"?>"
+        ),
+        XMP_NAMESPACE, properties
+    )
+}
+
+/// Text content of the first `<element>...</element>`
+fn xml_element<'a>(xml: &'a str, element: &str) -> Option<&'a str> {
+    let open = format!("<{}>", element);
+    let close = format!("</{}>", element);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)? + start;
+    Some(&xml[start..end])
+}
+
+fn read_xmp(xml: &str) -> ImageProvenance {
+    let mut provenance = ImageProvenance::default();
+    for (key, property) in FIELDS {
+        let value = if key == KEY_DESCRIPTION {
+            // Language alternatives: take the first entry
+            xml_element(xml, property).and_then(|alt| {
+                let start = alt.find("<rdf:li")?;
+                let text_start = alt[start..].find('>')? + start + 1;
+                let text_end = alt[text_start..].find("</rdf:li>")? + text_start;
+                Some(&alt[text_start..text_end])
+            })
+        } else {
+            xml_element(xml, property)
+        };
+        if let Some(value) = value {
+            provenance.set(key, unescape_xml(value));
+        }
+    }
+    provenance
+}
+
+// --- JPEG ---
+
+/// Iterate (marker, payload start, payload end) over JPEG header segments up to the scan
+fn jpeg_segments(bytes: &[u8]) -> impl Iterator<Item = (u8, usize, usize)> + '_ {
+    let mut pos = 2;
+    std::iter::from_fn(move || {
+        let header = bytes.get(pos..pos + 4)?;
+        // Start of scan: entropy-coded data follows
+        if header[0] != 0xff || header[1] == 0xda {
+            return None;
+        }
+        let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+        if len < 2 || pos + 2 + len > bytes.len() {
+            return None;
+        }
+        let segment = (header[1], pos + 4, pos + 2 + len);
+        pos += 2 + len;
+        Some(segment)
+    })
+}
+
+fn jpeg_app1(header: &[u8], payload: &[u8]) -> Result<Vec<u8>, String> {
+    let len = header.len() + payload.len() + 2;
+    if len > u16::MAX as usize {
+        return Err("Metadata is too large for a JPEG segment".to_string());
+    }
+    let mut segment = vec![0xff, 0xe1];
+    segment.extend_from_slice(&(len as u16).to_be_bytes());
+    segment.extend_from_slice(header);
+    segment.extend_from_slice(payload);
+    Ok(segment)
+}
+
+fn embed_jpeg(bytes: Vec<u8>, provenance: &ImageProvenance) -> Result<Vec<u8>, String> {
+    if !bytes.starts_with(&[0xff, 0xd8]) {
+        return Err("Not a JPEG file".to_string());
+    }
+    // EXIF/XMP go after SOI and the JFIF APP0 segment
+    let insert_at = jpeg_segments(&bytes)
+        .take_while(|(marker, _, _)| *marker == 0xe0)
+        .last()
+        .map_or(2, |(_, _, end)| end);
+
+    let mut segments = Vec::new();
+    if let Some(exif) = exif_block(provenance) {
+        segments.extend(jpeg_app1(EXIF_HEADER, &exif)?);
+    }
+    segments.extend(jpeg_app1(XMP_HEADER, xmp_packet(provenance).as_bytes())?);
+
+    let mut out = Vec::with_capacity(bytes.len() + segments.len());
+    out.extend_from_slice(&bytes[..insert_at]);
+    out.extend(segments);
+    out.extend_from_slice(&bytes[insert_at..]);
+    Ok(out)
+}
+
+fn read_jpeg(bytes: &[u8]) -> ImageProvenance {
+    jpeg_segments(bytes)
+        .filter(|(marker, _, _)| *marker == 0xe1)
+        .find_map(|(_, start, end)| bytes[start..end].strip_prefix(XMP_HEADER))
+        .map(|xmp| read_xmp(&String::from_utf8_lossy(xmp)))
+        .unwrap_or_default()
+}
+
+// --- Pixel density ---
+
+/// Logical pixels per inch: macOS lays out in 72 dpi points, other platforms in 96 dpi pixels
+#[cfg(target_os = "macos")]
+const BASE_DPI: f32 = 72.0;
+#[cfg(not(target_os = "macos"))]
+const BASE_DPI: f32 = 96.0;
+
+/// Physical resolution of pixels captured at a display scale factor, so documents
+/// place HiDPI captures at their on-screen size instead of 2x
+pub fn dpi_for_scale(scale_factor: f32) -> f32 {
+    BASE_DPI * if scale_factor > 0.0 { scale_factor } else { 1.0 }
+}
+
+fn png_density(bytes: Vec<u8>, dpi: f32) -> Result<Vec<u8>, String> {
+    let pixels_per_meter = (dpi / 0.0254).round() as u32;
+    let mut data = Vec::with_capacity(9);
+    data.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    data.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    // Unit: meter
+    data.push(1);
+    insert_png_chunks(bytes, png_chunk(b"pHYs", &data))
+}
+
+fn jpeg_density(mut bytes: Vec<u8>, dpi: f32) -> Result<Vec<u8>, String> {
+    if !bytes.starts_with(&[0xff, 0xd8]) {
+        return Err("Not a JPEG file".to_string());
+    }
+    let dpi = (dpi.round() as u16).max(1).to_be_bytes();
+
+    // JFIF payload: identifier, version, units, X density, Y density, thumbnail size
+    let jfif = jpeg_segments(&bytes)
+        .find(|(marker, start, end)| *marker == 0xe0 && end - start >= 12 && bytes[*start..].starts_with(b"JFIF\0"))
+        .map(|(_, start, _)| start);
+    match jfif {
+        Some(start) => {
+            // Units: dots per inch
+            bytes[start + 7] = 1;
+            bytes[start + 8..start + 10].copy_from_slice(&dpi);
+            bytes[start + 10..start + 12].copy_from_slice(&dpi);
+        }
+        None => {
+            let mut segment = vec![0xff, 0xe0, 0x00, 0x10];
+            segment.extend_from_slice(b"JFIF\0\x01\x02\x01");
+            segment.extend_from_slice(&dpi);
+            segment.extend_from_slice(&dpi);
+            segment.extend_from_slice(&[0, 0]);
+            bytes.splice(2..2, segment);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Record the physical resolution in PNG pHYs or the JPEG JFIF header
+/// Other formats are returned unchanged
+pub fn set_density(bytes: Vec<u8>, spec: &ExportSpec, dpi: f32) -> Result<Vec<u8>, String> {
+    match spec {
+        ExportSpec::Png { .. } => png_density(bytes, dpi),
+        ExportSpec::Jpeg { .. } => jpeg_density(bytes, dpi),
+        _ => Ok(bytes),
+    }
+}
+
+// --- WebP ---
+
+/// Iterate (fourcc, data) over the chunks of a RIFF WebP file
+fn webp_chunks(bytes: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut pos = 12;
+    std::iter::from_fn(move || {
+        let header = bytes.get(pos..pos + 8)?;
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let data = bytes.get(pos + 8..pos + 8 + len)?;
+        // Odd-sized chunks are padded to an even length
+        pos += 8 + len + (len & 1);
+        Some((&header[..4], data))
+    })
+}
+
+fn push_webp_chunk(out: &mut Vec<u8>, fourcc: &[u8], data: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+/// Canvas size and alpha flag of a simple-format (VP8L or VP8) bitstream
+fn webp_canvas(fourcc: &[u8], data: &[u8]) -> Option<(u32, u32, bool)> {
+    match fourcc {
+        b"VP8L" if data.len() >= 5 && data[0] == 0x2f => {
+            let bits = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1, bits & (1 << 28) != 0))
+        }
+        b"VP8 " if data.len() >= 10 => {
+            let width = u16::from_le_bytes([data[6], data[7]]) & 0x3fff;
+            let height = u16::from_le_bytes([data[8], data[9]]) & 0x3fff;
+            Some((width as u32, height as u32, false))
+        }
+        _ => None,
+    }
+}
+
+fn embed_webp(bytes: Vec<u8>, provenance: &ImageProvenance) -> Result<Vec<u8>, String> {
+    if bytes.len() < 20 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return Err("Not a WebP file".to_string());
+    }
+
+    // Metadata needs the extended format: a VP8X header with EXIF/XMP flags
+    const FLAG_ALPHA: u8 = 0x10;
+    const FLAG_EXIF: u8 = 0x08;
+    const FLAG_XMP: u8 = 0x04;
+    let mut vp8x: Option<[u8; 10]> = None;
+    let mut body = Vec::new();
+    for (fourcc, data) in webp_chunks(&bytes) {
+        match fourcc {
+            b"VP8X" if data.len() == 10 => vp8x = data.try_into().ok(),
+            // Replaced below
+            b"EXIF" | b"XMP " => {}
+            _ => {
+                if vp8x.is_none() {
+                    let (width, height, alpha) =
+                        webp_canvas(fourcc, data).ok_or("Unsupported WebP bitstream")?;
+                    let mut header = [0u8; 10];
+                    header[0] = if alpha { FLAG_ALPHA } else { 0 };
+                    header[4..7].copy_from_slice(&(width - 1).to_le_bytes()[..3]);
+                    header[7..10].copy_from_slice(&(height - 1).to_le_bytes()[..3]);
+                    vp8x = Some(header);
+                }
+                push_webp_chunk(&mut body, fourcc, data);
+            }
+        }
+    }
+    let mut vp8x = vp8x.ok_or("WebP file has no image data")?;
+
+    let exif = exif_block(provenance);
+    vp8x[0] |= FLAG_XMP;
+    if exif.is_some() {
+        vp8x[0] |= FLAG_EXIF;
+    }
+    let mut chunks = Vec::with_capacity(body.len() + 1024);
+    push_webp_chunk(&mut chunks, b"VP8X", &vp8x);
+    chunks.extend(body);
+    if let Some(exif) = exif {
+        push_webp_chunk(&mut chunks, b"EXIF", &exif);
+    }
+    push_webp_chunk(&mut chunks, b"XMP ", xmp_packet(provenance).as_bytes());
+
+    let mut out = Vec::with_capacity(chunks.len() + 12);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&((chunks.len() + 4) as u32).to_le_bytes());
+    out.extend_from_slice(b"WEBP");
+    out.extend(chunks);
+    Ok(out)
+}
+
+fn read_webp(bytes: &[u8]) -> ImageProvenance {
+    webp_chunks(bytes)
+        .find(|(fourcc, _)| *fourcc == b"XMP ")
+        .map(|(_, xmp)| read_xmp(&String::from_utf8_lossy(xmp)))
+        .unwrap_or_default()
+}
+
+/// Embed provenance into encoded image bytes
+/// PNG, JPEG and WebP carry metadata; other formats are returned unchanged
+pub fn embed(bytes: Vec<u8>, spec: &ExportSpec, provenance: &ImageProvenance) -> Result<Vec<u8>, String> {
+    match spec {
+        ExportSpec::Png { .. } => embed_png(bytes, provenance),
+        ExportSpec::Jpeg { .. } => embed_jpeg(bytes, provenance),
+        ExportSpec::Webp => embed_webp(bytes, provenance),
+        _ => Ok(bytes),
+    }
+}
+
+/// Read provenance back from encoded image bytes (empty when there is none)
+pub fn read(bytes: &[u8]) -> Result<ImageProvenance, String> {
+    if bytes.starts_with(PNG_SIGNATURE) {
+        Ok(read_png(bytes))
+    } else if bytes.starts_with(&[0xff, 0xd8]) {
+        Ok(read_jpeg(bytes))
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Ok(read_webp(bytes))
+    } else {
+        Err("Metadata can only be read from PNG, JPEG and WebP files".to_string())
+    }
+}
+
+pub fn read_file(path: &str) -> Result<ImageProvenance, String> {
+    let size = std::fs::metadata(Path::new(path))
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?
+        .len();
+    if size > MAX_INPUT_SIZE {
+        return Err("File is too large".to_string());
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    read(&bytes)
+}
+
+/// Read embedded provenance from a saved image (for library/history indexing)
+#[tauri::command]
+pub async fn read_image_metadata(path: String) -> Result<ImageProvenance, String> {
+    read_file(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{encode, PngFilter};
+    use image::{Rgba, RgbaImage};
+
+    fn png_spec() -> ExportSpec {
+        ExportSpec::Png { compression: None, filter: PngFilter::default(), optimize: false }
+    }
+
+    fn jpeg_spec() -> ExportSpec {
+        ExportSpec::Jpeg { quality: 90 }
+    }
+
+    fn encoded(spec: &ExportSpec) -> Vec<u8> {
+        let image = RgbaImage::from_fn(16, 12, |x, y| Rgba([(x * 16) as u8, (y * 20) as u8, 80, 255]));
+        encode(&image, spec).unwrap()
+    }
+
+    fn provenance() -> ImageProvenance {
+        ImageProvenance {
+            captured_at: Some("2026-03-14T09:26:53+01:00".to_string()),
+            app_name: Some("Firefox".to_string()),
+            window_title: Some("Café <menu> & \"résumé\" — 日本語".to_string()),
+            monitor_name: Some("DELL U2720Q".to_string()),
+            scale_factor: Some(1.5),
+            app_version: Some("1.2.3".to_string()),
+            description: Some("Bug report: login fails".to_string()),
+        }
+    }
+
+    fn assert_round_trip(spec: ExportSpec) {
+        let bytes = embed(encoded(&spec), &spec, &provenance()).unwrap();
+        assert_eq!(read(&bytes).unwrap(), provenance());
+        // The file must still decode after splicing
+        image::load_from_memory(&bytes).unwrap();
+    }
+
+    #[test]
+    fn png_round_trip() {
+        assert_round_trip(png_spec());
+    }
+
+    #[test]
+    fn jpeg_round_trip() {
+        assert_round_trip(jpeg_spec());
+    }
+
+    #[test]
+    fn webp_round_trip() {
+        assert_round_trip(ExportSpec::Webp);
+    }
+
+    #[test]
+    fn png_uses_itxt_only_for_non_ascii_values() {
+        let bytes = embed(encoded(&png_spec()), &png_spec(), &provenance()).unwrap();
+        let kinds: Vec<(String, &[u8])> = png_chunks(&bytes)
+            .filter(|(kind, _)| *kind == b"tEXt" || *kind == b"iTXt")
+            .map(|(kind, data)| (String::from_utf8_lossy(data).into_owned(), kind))
+            .collect();
+        for (data, kind) in kinds {
+            let expected: &[u8] = if data.starts_with(KEY_WINDOW) { b"iTXt" } else { b"tEXt" };
+            assert_eq!(kind, expected, "{}", data);
+        }
+    }
+
+    #[test]
+    fn jpeg_gets_exif_and_xmp_after_jfif() {
+        let bytes = embed(encoded(&jpeg_spec()), &jpeg_spec(), &provenance()).unwrap();
+        let segments: Vec<(u8, &[u8])> = jpeg_segments(&bytes)
+            .map(|(marker, start, end)| (marker, &bytes[start..end]))
+            .collect();
+        assert_eq!(segments[0].0, 0xe0);
+        assert!(segments[1].1.starts_with(EXIF_HEADER));
+        assert!(segments[2].1.starts_with(XMP_HEADER));
+    }
+
+    #[test]
+    fn webp_gets_extended_header_flags() {
+        let bytes = embed(encoded(&ExportSpec::Webp), &ExportSpec::Webp, &provenance()).unwrap();
+        let (fourcc, vp8x) = webp_chunks(&bytes).next().unwrap();
+        assert_eq!(fourcc, b"VP8X");
+        // XMP and EXIF flags, 16x12 canvas
+        assert_eq!(vp8x[0] & 0x0c, 0x0c);
+        assert_eq!(&vp8x[4..10], &[15, 0, 0, 11, 0, 0]);
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize, bytes.len() - 8);
+    }
+
+    #[test]
+    fn embedding_twice_replaces_webp_metadata() {
+        let spec = ExportSpec::Webp;
+        let first = embed(encoded(&spec), &spec, &ImageProvenance::now(Some("old".to_string()))).unwrap();
+        let bytes = embed(first, &spec, &provenance()).unwrap();
+        assert_eq!(webp_chunks(&bytes).filter(|(fourcc, _)| *fourcc == b"XMP ").count(), 1);
+        assert_eq!(read(&bytes).unwrap(), provenance());
+    }
+
+    /// JPEG bytes with the JFIF APP0 segment removed
+    fn without_jfif(bytes: Vec<u8>) -> Vec<u8> {
+        let (_, _, end) = jpeg_segments(&bytes).find(|(marker, _, _)| *marker == 0xe0).unwrap();
+        [&bytes[..2], &bytes[end..]].concat()
+    }
+
+    /// JFIF (units, X density, Y density)
+    fn jfif_density(bytes: &[u8]) -> (u8, u16, u16) {
+        let (_, start, _) = jpeg_segments(bytes).find(|(marker, _, _)| *marker == 0xe0).unwrap();
+        let field = |at: usize| u16::from_be_bytes([bytes[start + at], bytes[start + at + 1]]);
+        (bytes[start + 7], field(8), field(10))
+    }
+
+    #[test]
+    fn dpi_follows_scale_factor() {
+        assert_eq!(dpi_for_scale(2.0), BASE_DPI * 2.0);
+        assert_eq!(dpi_for_scale(0.0), BASE_DPI);
+    }
+
+    #[test]
+    fn png_density_writes_phys() {
+        let bytes = set_density(encoded(&png_spec()), &png_spec(), 192.0).unwrap();
+        let decoder = png::Decoder::new(std::io::Cursor::new(&bytes));
+        let reader = decoder.read_info().unwrap();
+        let dims = reader.info().pixel_dims.unwrap();
+        // 192 dpi = 7559 pixels per meter
+        assert_eq!((dims.xppu, dims.yppu, dims.unit), (7559, 7559, png::Unit::Meter));
+    }
+
+    #[test]
+    fn jpeg_density_rewrites_jfif() {
+        let bytes = set_density(encoded(&jpeg_spec()), &jpeg_spec(), 144.0).unwrap();
+        assert_eq!(jfif_density(&bytes), (1, 144, 144));
+        image::load_from_memory(&bytes).unwrap();
+    }
+
+    #[test]
+    fn jpeg_density_inserts_missing_jfif() {
+        let bare = without_jfif(encoded(&jpeg_spec()));
+        assert!(jpeg_segments(&bare).all(|(marker, _, _)| marker != 0xe0));
+
+        let bytes = set_density(bare, &jpeg_spec(), 96.0).unwrap();
+        assert_eq!(jfif_density(&bytes), (1, 96, 96));
+        image::load_from_memory(&bytes).unwrap();
+    }
+
+    #[test]
+    fn density_survives_metadata_embedding() {
+        let spec = jpeg_spec();
+        let bytes = set_density(encoded(&spec), &spec, 144.0).unwrap();
+        let bytes = embed(bytes, &spec, &provenance()).unwrap();
+        assert_eq!(jfif_density(&bytes), (1, 144, 144));
+        assert_eq!(read(&bytes).unwrap(), provenance());
+    }
+
+    #[test]
+    fn webp_density_is_unchanged() {
+        // WebP has no density field; the VP8X header stays as embedding left it
+        let spec = ExportSpec::Webp;
+        let bytes = embed(encoded(&spec), &spec, &provenance()).unwrap();
+        assert_eq!(set_density(bytes.clone(), &spec, 192.0).unwrap(), bytes);
+    }
+
+    #[test]
+    fn unmarked_files_read_as_empty() {
+        for spec in [png_spec(), jpeg_spec(), ExportSpec::Webp] {
+            assert_eq!(read(&encoded(&spec)).unwrap(), ImageProvenance::default());
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_formats() {
+        assert!(read(&encoded(&ExportSpec::Bmp)).is_err());
+        assert!(embed_png(b"not an image".to_vec(), &provenance()).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+// Single-instance handling - a second launch forwards its arguments to the
+// running app instead of starting another tray icon and shortcut set
+// `beautyfullshot --capture-region` / `--show` reuse the tray events; image files
+// (from argv or an OS file association) are decoded and opened in the editor
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Runtime};
+
+use crate::{image_import, tray};
+
+/// Actions from the first launch, held until the frontend listeners are ready
+static PENDING_ACTIONS: Mutex<Vec<LaunchAction>> = Mutex::new(Vec::new());
+
+/// Set once the frontend has flushed the pending actions
+static FRONTEND_READY: AtomicBool = AtomicBool::new(false);
+
+/// Action requested through GUI launch arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchAction {
+    Show,
+    CaptureScreen,
+    CaptureRegion,
+    CaptureWindow,
+    OpenFile(PathBuf),
+}
+
+/// Parse GUI launch arguments (without the program name)
+/// Relative file paths are resolved against `cwd`, the launching process's directory
+pub fn parse_actions(args: &[String], cwd: &Path) -> Vec<LaunchAction> {
+    let mut actions = Vec::new();
+
+    for arg in args {
+        let action = match arg.as_str() {
+            "--show" => LaunchAction::Show,
+            "--capture-screen" => LaunchAction::CaptureScreen,
+            "--capture-region" => LaunchAction::CaptureRegion,
+            "--capture-window" => LaunchAction::CaptureWindow,
+            // Other flags come from the OS or the webview runtime; ignore them
+            other if other.starts_with('-') => continue,
+            path => {
+                let path = Path::new(path);
+                if path.is_absolute() {
+                    LaunchAction::OpenFile(path.to_path_buf())
+                } else {
+                    LaunchAction::OpenFile(cwd.join(path))
+                }
+            }
+        };
+        actions.push(action);
+    }
+
+    actions
+}
+
+/// Run a launch action through the same events the tray menu emits
+pub fn dispatch<R: Runtime>(app: &AppHandle<R>, action: &LaunchAction) {
+    println!("Launch action: {:?}", action);
+    match action {
+        LaunchAction::Show => tray::show_main_window(app),
+        LaunchAction::CaptureScreen => tray::emit_to_main(app, "tray-capture-screen"),
+        LaunchAction::CaptureRegion => tray::emit_to_main(app, "tray-capture-region"),
+        LaunchAction::CaptureWindow => tray::emit_to_main(app, "tray-capture-window"),
+        LaunchAction::OpenFile(path) => {
+            tray::show_main_window(app);
+            image_import::open_in_editor(app, vec![path.clone()]);
+        }
+    }
+}
+
+/// Handle arguments forwarded from a second launch
+/// A bare relaunch (no actions) brings the running window to the front
+pub fn handle_second_instance<R: Runtime>(app: &AppHandle<R>, args: Vec<String>, cwd: String) {
+    // Forwarded argv includes the program name
+    let args = args.get(1..).unwrap_or_default();
+    let actions = parse_actions(args, Path::new(&cwd));
+
+    if actions.is_empty() {
+        tray::show_main_window(app);
+        return;
+    }
+    for action in &actions {
+        dispatch(app, action);
+    }
+}
+
+/// Queue the first launch's actions; events emitted during setup would be lost
+pub fn queue_launch_actions(args: &[String]) {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let actions = parse_actions(args, &cwd);
+    PENDING_ACTIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .extend(actions);
+}
+
+/// Open files the OS hands over after launch (macOS file association / "Open With")
+/// Queued like launch args when they arrive before the frontend is listening
+#[cfg(target_os = "macos")]
+pub fn handle_opened_files<R: Runtime>(app: &AppHandle<R>, paths: Vec<PathBuf>) {
+    let actions = paths.into_iter().map(LaunchAction::OpenFile);
+    let mut pending = PENDING_ACTIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if FRONTEND_READY.load(Ordering::SeqCst) {
+        drop(pending);
+        for action in actions {
+            dispatch(app, &action);
+        }
+    } else {
+        pending.extend(actions);
+    }
+}
+
+/// Dispatch queued launch actions (called by the frontend once it listens for tray events)
+#[tauri::command]
+pub fn flush_launch_actions(app: AppHandle) {
+    FRONTEND_READY.store(true, Ordering::SeqCst);
+    let actions = std::mem::take(
+        &mut *PENDING_ACTIONS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    for action in &actions {
+        dispatch(&app, action);
+    }
+}
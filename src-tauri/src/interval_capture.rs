@@ -0,0 +1,302 @@
+// Interval / burst capture recorder
+// Captures a monitor (optionally cropped to a region) on a fixed schedule from a
+// background thread and writes numbered PNG frames into a timestamped folder
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::naming::{resolve_collision, CollisionPolicy};
+use crate::screenshot::{crop_region, image_to_png_bytes};
+use crate::{capture_backend, file_ops};
+
+/// Shortest allowed interval between frames
+const MIN_INTERVAL_MS: u64 = 100;
+
+/// Upper bound on frames per session to protect disk space
+const MAX_FRAMES: u32 = 10_000;
+
+/// Consecutive capture failures before the session gives up
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// How often the scheduler checks the stop flag while waiting
+const STOP_POLL: Duration = Duration::from_millis(50);
+
+/// Stop flag of the running session, if any
+static ACTIVE_SESSION: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// Region of the monitor to keep, relative to its top-left corner
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FrameRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Schedule for an interval or burst session
+/// The session ends at whichever of `max_frames` / `duration_secs` comes first
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntervalCaptureOptions {
+    /// Monitor to capture (primary when omitted)
+    pub monitor_id: Option<u32>,
+    pub region: Option<FrameRegion>,
+    pub interval_ms: u64,
+    pub max_frames: Option<u32>,
+    pub duration_secs: Option<u64>,
+    /// Parent folder for the session folder (Pictures/BeautyShot when omitted)
+    pub output_dir: Option<String>,
+}
+
+impl IntervalCaptureOptions {
+    /// Capture every `seconds` for `minutes`
+    pub fn every(seconds: u64, minutes: u64) -> Self {
+        Self {
+            monitor_id: None,
+            region: None,
+            interval_ms: seconds * 1000,
+            max_frames: None,
+            duration_secs: Some(minutes * 60),
+            output_dir: None,
+        }
+    }
+
+    /// Capture `frames` frames `interval_ms` apart
+    pub fn burst(frames: u32, interval_ms: u64) -> Self {
+        Self {
+            monitor_id: None,
+            region: None,
+            interval_ms,
+            max_frames: Some(frames),
+            duration_secs: None,
+            output_dir: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.interval_ms < MIN_INTERVAL_MS {
+            return Err(format!("Interval must be at least {} ms", MIN_INTERVAL_MS));
+        }
+        if self.max_frames.is_none() && self.duration_secs.is_none() {
+            return Err("Set max_frames and/or duration_secs".to_string());
+        }
+        if self.max_frames == Some(0) || self.duration_secs == Some(0) {
+            return Err("Session would capture no frames".to_string());
+        }
+        Ok(())
+    }
+
+    /// Total frames the session will take
+    fn frame_count(&self) -> u32 {
+        let by_duration = self
+            .duration_secs
+            .map(|secs| (secs * 1000 / self.interval_ms) as u32 + 1);
+        let frames = match (self.max_frames, by_duration) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => 0,
+        };
+        frames.min(MAX_FRAMES)
+    }
+}
+
+/// Per-frame progress payload
+#[derive(Debug, Clone, Serialize)]
+pub struct IntervalCaptureProgress {
+    pub frame: u32,
+    pub total: u32,
+    pub elapsed_ms: u64,
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Final payload when a session ends
+#[derive(Debug, Clone, Serialize)]
+pub struct IntervalCaptureSummary {
+    pub folder: String,
+    pub frames_saved: u32,
+    pub stopped: bool,
+    pub error: Option<String>,
+}
+
+/// Session folder: `<parent>/interval-2025-01-31_14-05-09`, with `-2`, `-3`, ...
+/// when a session already started in the same second
+/// The folder is created right away so the next session sees it as taken
+fn session_folder(options: &IntervalCaptureOptions) -> Result<PathBuf, String> {
+    let parent = match &options.output_dir {
+        Some(dir) => dir.clone(),
+        None => file_ops::get_pictures_dir()?,
+    };
+    let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let folder = PathBuf::from(parent).join(format!("interval-{}", stamp));
+    let folder = PathBuf::from(resolve_collision(folder, CollisionPolicy::Increment)?.path);
+    std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create session folder: {}", e))?;
+    Ok(folder)
+}
+
+/// Capture and save one frame, returning the saved path
+fn capture_frame(options: &IntervalCaptureOptions, path: &std::path::Path) -> Result<String, String> {
+    let capture = capture_backend::registry().capture_monitor(options.monitor_id)?;
+    let image = match options.region {
+        Some(r) => crop_region(&capture.image, r.x, r.y, r.width, r.height)?,
+        None => capture.image,
+    };
+    let bytes = image_to_png_bytes(&image)?;
+    file_ops::write_file(&path.to_string_lossy(), &bytes)
+}
+
+/// Wait until `deadline`, returning false if stopped meanwhile
+fn wait_until(deadline: Instant, stop: &AtomicBool) -> bool {
+    loop {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep(STOP_POLL.min(deadline - now));
+    }
+}
+
+fn run_session(app: AppHandle, options: IntervalCaptureOptions, folder: PathBuf, stop: Arc<AtomicBool>) {
+    let total = options.frame_count();
+    let interval = Duration::from_millis(options.interval_ms);
+    let started = Instant::now();
+    let mut frames_saved = 0;
+    let mut failures = 0;
+    let mut error = None;
+
+    for frame in 1..=total {
+        // Schedule from the start time so slow captures don't accumulate drift
+        if !wait_until(started + interval * (frame - 1), &stop) {
+            break;
+        }
+
+        let path = folder.join(format!("frame-{:05}.png", frame));
+        let result = capture_frame(&options, &path);
+        let progress = IntervalCaptureProgress {
+            frame,
+            total,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            path: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().cloned(),
+        };
+        let _ = app.emit("interval-capture-progress", &progress);
+
+        match result {
+            Ok(_) => {
+                frames_saved += 1;
+                failures = 0;
+            }
+            Err(e) => {
+                println!("Interval capture frame {} failed: {}", frame, e);
+                failures += 1;
+                if failures >= MAX_CONSECUTIVE_FAILURES {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+
+    // Only clear the slot if it still belongs to this session
+    {
+        let mut active = ACTIVE_SESSION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if active.as_ref().is_some_and(|flag| Arc::ptr_eq(flag, &stop)) {
+            *active = None;
+        }
+    }
+
+    let summary = IntervalCaptureSummary {
+        folder: folder.to_string_lossy().to_string(),
+        frames_saved,
+        stopped: stop.load(Ordering::SeqCst),
+        error,
+    };
+    println!("Interval capture finished: {:?}", summary);
+    let _ = app.emit("interval-capture-finished", &summary);
+}
+
+/// Start a session on a background thread, returning the session folder
+pub fn start(app: &AppHandle, options: IntervalCaptureOptions) -> Result<String, String> {
+    options.validate()?;
+
+    let mut active = ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if active.is_some() {
+        return Err("An interval capture is already running".to_string());
+    }
+
+    let folder = session_folder(&options)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    *active = Some(stop.clone());
+
+    let app = app.clone();
+    let session_folder = folder.clone();
+    thread::spawn(move || run_session(app, options, session_folder, stop));
+
+    Ok(folder.to_string_lossy().to_string())
+}
+
+/// Ask the running session to stop; returns false if none was running
+pub fn stop() -> bool {
+    let active = ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match active.as_ref() {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Start an interval or burst capture session
+#[tauri::command]
+pub fn start_interval_capture(app: AppHandle, options: IntervalCaptureOptions) -> Result<String, String> {
+    start(&app, options)
+}
+
+/// Stop the running interval capture session
+#[tauri::command]
+pub fn stop_interval_capture() -> bool {
+    stop()
+}
+
+/// Whether an interval capture session is running
+#[tauri::command]
+pub fn is_interval_capture_running() -> bool {
+    ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_to_back_sessions_get_separate_folders() {
+        let parent = std::env::temp_dir().join(format!("beautyshot-interval-{}", std::process::id()));
+        let mut options = IntervalCaptureOptions::burst(3, 100);
+        options.output_dir = Some(parent.to_string_lossy().to_string());
+
+        let first = session_folder(&options).unwrap();
+        let second = session_folder(&options).unwrap();
+        assert_ne!(first, second);
+        assert!(first.is_dir() && second.is_dir());
+
+        std::fs::remove_dir_all(parent).unwrap();
+    }
+}
@@ -4,9 +4,9 @@
 use std::sync::atomic::AtomicBool;
 #[cfg(target_os = "macos")]
 use std::sync::atomic::Ordering;
-use tauri::WindowEvent;
+use tauri::{DragDropEvent, Manager, WindowEvent};
 #[cfg(target_os = "macos")]
-use tauri::{Manager, RunEvent};
+use tauri::RunEvent;
 
 #[cfg(target_os = "macos")]
 use tauri::menu::{AboutMetadata, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
@@ -15,17 +15,45 @@ use tauri::menu::{AboutMetadata, MenuBuilder, MenuItemBuilder, PredefinedMenuIte
 /// vs just hide to tray (from Cmd+Q or window close)
 pub static SHOULD_QUIT: AtomicBool = AtomicBool::new(false);
 
+mod capture_backend;
+mod capture_store;
+mod cli;
 mod clipboard;
+mod export;
 mod file_ops;
+mod history;
+mod image_import;
+mod image_metadata;
+mod instance;
+mod interval_capture;
+mod multi_monitor;
+mod naming;
 mod overlay;
 mod permissions;
+mod png_optimize;
+mod recording;
+mod rich_clipboard;
 mod screenshot;
+mod scroll_capture;
 mod shortcuts;
+mod stitch;
+mod timed_capture;
 mod tray;
+mod virtual_desktop;
+mod window_frame;
+mod window_match;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Headless CLI (e.g. `beautyfullshot capture --out shot.png`) exits without the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(exit_code) = cli::run(&args) {
+        std::process::exit(exit_code);
+    }
+
     tauri::Builder::default()
+        // Must be registered first: a second launch forwards its args here and exits
+        .plugin(tauri_plugin_single_instance::init(instance::handle_second_instance))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_notification::init())
@@ -33,10 +61,32 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .setup(|app| {
+        .manage(capture_store::CaptureStore::default())
+        // Serve stored captures as raw bytes (capture://localhost/<id>)
+        .register_asynchronous_uri_scheme_protocol(
+            capture_store::CAPTURE_SCHEME,
+            capture_store::handle_capture_protocol,
+        )
+        .setup(move |app| {
+            // Open the on-disk capture history library; the app still runs without it
+            let library_dir = app.path().app_data_dir()?.join("library");
+            match history::HistoryStore::open(library_dir) {
+                Ok(history) => {
+                    app.manage(history);
+                }
+                Err(e) => println!("Capture history disabled: {}", e),
+            }
+
+            // Load filename template and auto-save settings
+            let naming_file = app.path().app_config_dir()?.join("naming.json");
+            app.manage(naming::NamingStore::open(naming_file));
+
             // Create system tray
             tray::create_tray(app.handle())?;
 
+            // Actions from our own launch args run once the frontend is listening
+            instance::queue_launch_actions(&args);
+
             // On macOS, create custom app menu to override Cmd+Q behavior
             #[cfg(target_os = "macos")]
             {
@@ -118,6 +168,13 @@ pub fn run() {
             Ok(())
         })
         .on_window_event(|window, event| {
+            // Image files dropped onto the main window open in the editor
+            if window.label() == "main" {
+                if let WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }) = event {
+                    image_import::open_in_editor(window.app_handle(), paths.clone());
+                }
+            }
+
             // Intercept close request on main window - hide instead of quit
             if window.label() == "main" {
                 if let WindowEvent::CloseRequested { api, .. } = event {
@@ -136,15 +193,31 @@ pub fn run() {
             }
         })
         .invoke_handler(tauri::generate_handler![
+            capture_backend::get_capture_backends,
+            capture_store::get_capture_rgba,
+            capture_store::get_capture_png,
+            capture_store::get_capture_metadata,
+            capture_store::list_captures,
+            capture_store::retain_capture,
+            capture_store::release_capture,
             screenshot::capture_fullscreen,
+            screenshot::capture_fullscreen_ref,
             screenshot::capture_region,
             screenshot::capture_window,
+            screenshot::capture_window_ref,
             screenshot::get_windows,
+            window_match::find_windows,
+            window_match::capture_window_matching,
+            window_match::capture_active_window,
+            window_match::capture_window_under_cursor,
             screenshot::get_window_thumbnail,
+            screenshot::get_window_thumbnail_ref,
             screenshot::get_monitors,
             screenshot::get_cursor_monitor,
             screenshot::capture_monitor,
+            screenshot::capture_monitor_ref,
             screenshot::capture_region_from_monitor,
+            screenshot::capture_region_from_monitor_ref,
             permissions::check_screen_permission,
             permissions::check_accessibility_permission,
             permissions::request_accessibility_permission,
@@ -155,15 +228,57 @@ pub fn run() {
             file_ops::save_file,
             file_ops::get_pictures_dir,
             file_ops::get_desktop_dir,
+            export::export_capture,
+            export::export_image,
+            image_metadata::read_image_metadata,
+            png_optimize::optimize_png_file,
+            naming::get_naming_settings,
+            naming::update_naming_settings,
+            naming::preview_capture_path,
+            naming::save_capture_named,
             shortcuts::update_shortcuts,
+            timed_capture::capture_after_delay,
+            timed_capture::cancel_delayed_capture,
+            interval_capture::start_interval_capture,
+            interval_capture::stop_interval_capture,
+            interval_capture::is_interval_capture_running,
+            recording::start_recording,
+            recording::stop_recording,
+            recording::is_recording,
+            scroll_capture::start_scroll_capture,
+            scroll_capture::stop_scroll_capture,
+            scroll_capture::stitch_captures,
             overlay::create_overlay_window,
             overlay::close_overlay_window,
             overlay::show_overlay_window_on_monitor,
             overlay::get_overlay_monitor,
+            overlay::get_overlay_windows,
+            overlay::show_window_picker_on_monitor,
+            overlay::show_desktop_overlay,
+            overlay::get_desktop_overlay,
+            overlay::finish_desktop_overlay,
+            virtual_desktop::get_desktop_layout,
+            virtual_desktop::capture_desktop_region_ref,
+            virtual_desktop::capture_all_screens,
+            virtual_desktop::capture_all_screens_ref,
+            multi_monitor::capture_each_monitor,
             overlay::capture_and_show_overlay,
             overlay::get_screenshot_data,
             overlay::clear_screenshot_data,
             clipboard::copy_image_to_clipboard,
+            clipboard::copy_capture_to_clipboard,
+            clipboard::copy_rgba_to_clipboard,
+            clipboard::read_image_from_clipboard,
+            image_import::open_image_file,
+            history::add_to_history,
+            history::list_history,
+            history::get_history_thumbnail,
+            history::reopen_history_entry,
+            history::set_history_pinned,
+            history::delete_history_entry,
+            history::get_history_settings,
+            history::update_history_settings,
+            instance::flush_launch_actions,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -191,6 +306,13 @@ pub fn run() {
                     let _ = _app.set_activation_policy(tauri::ActivationPolicy::Accessory);
                 }
 
+                // Files opened through the macOS file association arrive here, not in argv
+                #[cfg(target_os = "macos")]
+                RunEvent::Opened { urls } => {
+                    let paths = urls.iter().filter_map(|url| url.to_file_path().ok()).collect();
+                    instance::handle_opened_files(_app, paths);
+                }
+
                 // Handle macOS dock click to reopen window
                 #[cfg(target_os = "macos")]
                 RunEvent::Reopen { .. } => {
@@ -0,0 +1,144 @@
+// Per-monitor capture
+// Captures every monitor concurrently (one thread each) and keeps one image per
+// monitor, either in the capture store or saved as files named after the monitor.
+// A monitor that fails is reported on its own entry; the others still succeed.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::capture_backend::Capture;
+use crate::capture_store::{CaptureKind, CaptureRef, CaptureStore};
+use crate::export::{self, ExportOptions, ExportSpec, PngFilter};
+use crate::naming::{self, CollisionPolicy};
+use crate::screenshot::{self, monitor_metadata, MonitorInfo};
+use crate::{history, virtual_desktop};
+
+/// A monitor and the result of capturing it
+type MonitorCapture = (MonitorInfo, Result<Capture, String>);
+
+/// Outcome of capturing one monitor
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorCaptureResult {
+    pub monitor_id: u32,
+    pub monitor_name: String,
+    /// Stored capture (when not saving to a folder)
+    pub capture: Option<CaptureRef>,
+    /// Saved file (when saving to a folder)
+    pub path: Option<String>,
+    /// Why capturing or saving this monitor failed
+    pub error: Option<String>,
+}
+
+impl MonitorCaptureResult {
+    fn new(monitor: &MonitorInfo) -> Self {
+        Self {
+            monitor_id: monitor.id,
+            monitor_name: monitor.name.clone(),
+            capture: None,
+            path: None,
+            error: None,
+        }
+    }
+}
+
+/// File name for a monitor's image: its sanitized name, or its position when unnamed
+fn file_name(monitor: &MonitorInfo, index: usize, ext: &str) -> String {
+    let name = monitor.name.trim();
+    let stem = if name.is_empty() {
+        format!("monitor-{}", index + 1)
+    } else {
+        name.to_string()
+    };
+    naming::sanitize_component(&format!("{}.{}", stem, ext))
+}
+
+/// Default per-monitor file format
+pub fn default_spec() -> ExportSpec {
+    ExportSpec::Png {
+        compression: None,
+        filter: PngFilter::default(),
+        optimize: false,
+    }
+}
+
+/// Capture all monitors in parallel and pair each result with its monitor
+fn capture_all() -> Result<Vec<MonitorCapture>, String> {
+    let monitors = screenshot::get_monitors()?;
+    if monitors.is_empty() {
+        return Err("No monitors found".to_string());
+    }
+    let captures = virtual_desktop::capture_monitors(&monitors);
+    Ok(monitors.into_iter().zip(captures).collect())
+}
+
+/// Capture every monitor and save one file per monitor into `directory`
+/// Existing files are kept; new names get -2, -3, ... appended
+pub fn save_each_monitor(directory: &Path, spec: &ExportSpec) -> Result<Vec<MonitorCaptureResult>, String> {
+    let results = capture_all()?
+        .into_iter()
+        .enumerate()
+        .map(|(index, (monitor, capture))| {
+            let mut result = MonitorCaptureResult::new(&monitor);
+            let saved = capture.and_then(|capture| {
+                let metadata = monitor_metadata(CaptureKind::Monitor, Some(monitor.id), capture.backend);
+                let target = directory.join(file_name(&monitor, index, spec.extension()));
+                let resolved = naming::resolve_collision(target, CollisionPolicy::Increment)?;
+                export::export(&capture.image, spec, &resolved.path, &ExportOptions::default(), Some(&metadata))
+            });
+            match saved {
+                Ok(export) => result.path = Some(export.path),
+                Err(e) => {
+                    println!("Failed to capture or save monitor {} ({}): {}", monitor.id, monitor.name, e);
+                    result.error = Some(e);
+                }
+            }
+            result
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Capture every monitor into the capture store, one capture per monitor
+fn store_each_monitor(app: &AppHandle) -> Result<Vec<MonitorCaptureResult>, String> {
+    let store = app.state::<CaptureStore>();
+    let results = capture_all()?
+        .into_iter()
+        .map(|(monitor, capture)| {
+            let mut result = MonitorCaptureResult::new(&monitor);
+            match capture {
+                Ok(capture) => {
+                    let metadata = monitor_metadata(CaptureKind::Monitor, Some(monitor.id), capture.backend);
+                    let capture_ref = store.insert(capture.image, metadata);
+                    history::auto_record(app, &capture_ref);
+                    naming::auto_save(app, &capture_ref);
+                    result.capture = Some(capture_ref);
+                }
+                Err(e) => {
+                    println!("Failed to capture monitor {} ({}): {}", monitor.id, monitor.name, e);
+                    result.error = Some(e);
+                }
+            }
+            result
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Capture every monitor concurrently, one image per monitor
+/// With `directory` the images are saved there (PNG unless `spec` says otherwise) and
+/// not kept in the store; failures are reported per monitor
+#[tauri::command]
+pub async fn capture_each_monitor(
+    app: AppHandle,
+    directory: Option<String>,
+    spec: Option<ExportSpec>,
+) -> Result<Vec<MonitorCaptureResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || match directory {
+        Some(directory) => save_each_monitor(Path::new(&directory), &spec.unwrap_or_else(default_spec)),
+        None => store_each_monitor(&app),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
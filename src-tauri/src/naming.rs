@@ -0,0 +1,632 @@
+// Filename templating and auto-save destinations
+// Templates like `{date:%Y-%m-%d}/{app}-{title}-{counter}.{ext}` are rendered from
+// capture metadata, sanitized for the current platform, and resolved against a
+// configurable base folder with a collision policy
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::capture_store::{CaptureMetadata, CaptureRef, CaptureStore};
+use crate::export::{self, ExportOptions, ExportResult, ExportSpec, PngFilter};
+use crate::image_metadata::EmbedOptions;
+use crate::file_ops;
+
+/// Default template: one folder per day, named after the source app and window
+pub const DEFAULT_TEMPLATE: &str = "{date:%Y-%m-%d}/{app}-{title}-{counter}.{ext}";
+
+/// Longest file or folder name produced (bytes), below common filesystem limits
+const MAX_COMPONENT_LEN: usize = 200;
+
+/// Give up incrementing after this many existing files
+const MAX_INCREMENT: u32 = 10_000;
+
+/// What to do when the target file already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollisionPolicy {
+    /// Append -2, -3, ... before the extension
+    #[default]
+    Increment,
+    Overwrite,
+    /// Report the conflict so the frontend can ask the user
+    Ask,
+}
+
+/// Naming and auto-save preferences, persisted as JSON in the app config folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamingSettings {
+    /// Root folder for saves (Pictures/BeautyShot when unset)
+    pub base_dir: Option<String>,
+    pub template: String,
+    pub collision: CollisionPolicy,
+    /// Save every capture to disk as soon as it is taken
+    pub auto_save: bool,
+    pub auto_save_format: ExportSpec,
+    /// Embed capture provenance (time, app, window, monitor) in saved files
+    pub embed_metadata: bool,
+    /// Next value of `{counter}`
+    pub counter: u64,
+}
+
+impl Default for NamingSettings {
+    fn default() -> Self {
+        Self {
+            base_dir: None,
+            template: DEFAULT_TEMPLATE.to_string(),
+            collision: CollisionPolicy::default(),
+            auto_save: false,
+            auto_save_format: ExportSpec::Png {
+                compression: None,
+                filter: PngFilter::default(),
+                optimize: false,
+            },
+            embed_metadata: false,
+            counter: 1,
+        }
+    }
+}
+
+/// Where a save would go
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedPath {
+    pub path: String,
+    /// The file exists and the policy is `Ask`
+    pub conflict: bool,
+}
+
+/// Outcome of a templated save
+#[derive(Debug, Clone, Serialize)]
+pub struct NamedSave {
+    /// Set when the file was written
+    pub result: Option<ExportResult>,
+    /// Set when the user must confirm overwriting this path
+    pub conflict: Option<String>,
+}
+
+/// Values available to template tokens
+pub struct TemplateContext<'a> {
+    pub metadata: &'a CaptureMetadata,
+    pub width: u32,
+    pub height: u32,
+    pub counter: u64,
+    pub ext: &'a str,
+}
+
+impl TemplateContext<'_> {
+    fn token(&self, name: &str, arg: Option<&str>) -> Result<String, String> {
+        let m = self.metadata;
+        let value = match name {
+            "date" => self.format_time(arg.unwrap_or("%Y-%m-%d"))?,
+            "time" => self.format_time(arg.unwrap_or("%H-%M-%S"))?,
+            // Fall back to the monitor for screen captures
+            "app" => m
+                .app_name
+                .clone()
+                .or_else(|| m.monitor_name.clone())
+                .unwrap_or_else(|| "Screenshot".to_string()),
+            "title" => m.window_title.clone().unwrap_or_default(),
+            "monitor" => m.monitor_name.clone().unwrap_or_default(),
+            "kind" => serde_json::to_value(m.kind)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            "width" => self.width.to_string(),
+            "height" => self.height.to_string(),
+            "counter" => {
+                let width = match arg {
+                    Some(w) => w.parse::<usize>().map_err(|_| format!("Invalid counter width '{}'", w))?,
+                    None => 1,
+                };
+                format!("{:0width$}", self.counter, width = width)
+            }
+            "ext" => self.ext.to_string(),
+            other => return Err(format!("Unknown template token '{{{}}}'", other)),
+        };
+        Ok(value)
+    }
+
+    fn format_time(&self, format: &str) -> Result<String, String> {
+        let time = Local
+            .timestamp_millis_opt(self.metadata.captured_at as i64)
+            .single()
+            .unwrap_or_else(Local::now);
+        let mut out = String::new();
+        // Writing (rather than to_string) turns bad format specifiers into an error
+        write!(out, "{}", time.format(format)).map_err(|_| format!("Invalid date format '{}'", format))?;
+        Ok(out)
+    }
+}
+
+/// Render one path component, substituting `{token}` / `{token:arg}`
+fn render_component(component: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = component;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed '{{' in template component '{}'", component))?;
+        let token = &rest[start + 1..start + end];
+        let (name, arg) = match token.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (token, None),
+        };
+        // Token values never create folders
+        out.push_str(&ctx.token(name, arg)?.replace(['/', '\\'], "_"));
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Characters not allowed in file names on this platform
+fn is_forbidden(c: char) -> bool {
+    if c.is_control() {
+        return true;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+    }
+    #[cfg(target_os = "macos")]
+    {
+        matches!(c, '/' | ':')
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        c == '/'
+    }
+}
+
+/// Device names Windows reserves regardless of extension
+#[cfg(target_os = "windows")]
+fn is_reserved(stem: &str) -> bool {
+    let upper = stem.to_ascii_uppercase();
+    matches!(upper.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((upper.starts_with("COM") || upper.starts_with("LPT"))
+            && upper.len() == 4
+            && upper.as_bytes()[3].is_ascii_digit())
+}
+
+/// Truncate to at most `max` bytes on a char boundary
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Replace each run of dots ("Loading...") with '_', since file_ops rejects any
+/// path containing ".." as directory traversal
+fn replace_dot_runs(stem: &str) -> String {
+    let mut out = String::with_capacity(stem.len());
+    let mut chars = stem.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '.' && chars.peek() == Some(&'.') {
+            while chars.next_if_eq(&'.').is_some() {}
+            out.push('_');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Make a single file or folder name safe for this platform
+/// Empty placeholders leave runs of separators behind; those are collapsed
+pub fn sanitize_component(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| if is_forbidden(c) { '_' } else { c })
+        .collect();
+
+    // Collapse separator runs like "--" left by empty tokens
+    let mut collapsed = String::with_capacity(replaced.len());
+    for c in replaced.chars() {
+        if matches!(c, '-' | '_' | ' ') && collapsed.ends_with(c) {
+            continue;
+        }
+        collapsed.push(c);
+    }
+
+    // Split off the extension so trimming/truncation keeps it intact
+    let (stem, ext) = match collapsed.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() && !ext.contains(' ') => (stem.to_string(), Some(ext.to_string())),
+        _ => (collapsed.clone(), None),
+    };
+    let stem = replace_dot_runs(&stem);
+    let ext_len = ext.as_ref().map(|e| e.len() + 1).unwrap_or(0);
+    let stem = truncate_bytes(
+        stem.trim_matches(|c: char| matches!(c, '-' | '_' | ' ' | '.')),
+        MAX_COMPONENT_LEN.saturating_sub(ext_len),
+    )
+    .trim_end_matches(['.', ' '])
+    .to_string();
+
+    let mut stem = if stem.is_empty() { "_".to_string() } else { stem };
+    #[cfg(target_os = "windows")]
+    if is_reserved(&stem) {
+        stem.insert(0, '_');
+    }
+    #[cfg(not(target_os = "windows"))]
+    if stem == "." || stem == ".." {
+        stem = "_".to_string();
+    }
+
+    match ext {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem,
+    }
+}
+
+/// Render a template into a relative path of sanitized components
+pub fn render_template(template: &str, ctx: &TemplateContext) -> Result<PathBuf, String> {
+    let mut path = PathBuf::new();
+    for component in template.split(['/', '\\']).filter(|c| !c.trim().is_empty()) {
+        path.push(sanitize_component(&render_component(component, ctx)?));
+    }
+    if path.as_os_str().is_empty() {
+        return Err("Template produced an empty file name".to_string());
+    }
+    Ok(path)
+}
+
+/// `shot.png` -> `shot-2.png`, `shot-3.png`, ... until a free name is found
+fn increment_path(path: &Path) -> Result<PathBuf, String> {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = path.extension().map(|e| e.to_string_lossy().to_string());
+    for n in 2..=MAX_INCREMENT {
+        let name = match &ext {
+            Some(ext) => format!("{}-{}.{}", stem, n, ext),
+            None => format!("{}-{}", stem, n),
+        };
+        let candidate = path.with_file_name(name);
+        if !candidate.exists() {
+            return Ok(candidate);
+        }
+    }
+    Err("Too many files with the same name".to_string())
+}
+
+/// Apply the collision policy to a target path
+pub fn resolve_collision(path: PathBuf, policy: CollisionPolicy) -> Result<ResolvedPath, String> {
+    let (path, conflict) = match policy {
+        _ if !path.exists() => (path, false),
+        CollisionPolicy::Increment => (increment_path(&path)?, false),
+        CollisionPolicy::Overwrite => (path, false),
+        CollisionPolicy::Ask => (path, true),
+    };
+    Ok(ResolvedPath {
+        path: path.to_string_lossy().to_string(),
+        conflict,
+    })
+}
+
+/// Naming settings and counter, managed as Tauri state
+pub struct NamingStore {
+    file: PathBuf,
+    settings: Mutex<NamingSettings>,
+}
+
+impl NamingStore {
+    /// Load settings from `file`, falling back to defaults
+    pub fn open(file: PathBuf) -> Self {
+        let settings = fs::read(&file)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            file,
+            settings: Mutex::new(settings),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, NamingSettings> {
+        self.settings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn settings(&self) -> NamingSettings {
+        self.lock().clone()
+    }
+
+    fn persist(&self, settings: &NamingSettings) -> Result<(), String> {
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create config folder: {}", e))?;
+        }
+        let json = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+        fs::write(&self.file, json).map_err(|e| format!("Failed to save naming settings: {}", e))
+    }
+
+    /// Validate, replace and persist settings
+    pub fn set_settings(&self, settings: NamingSettings) -> Result<(), String> {
+        // Render once against dummy metadata to surface template errors early
+        let sample = CaptureMetadata::new(crate::capture_store::CaptureKind::Monitor, "");
+        render_template(
+            &settings.template,
+            &TemplateContext {
+                metadata: &sample,
+                width: 1,
+                height: 1,
+                counter: settings.counter,
+                ext: "png",
+            },
+        )?;
+        self.persist(&settings)?;
+        *self.lock() = settings;
+        Ok(())
+    }
+
+    /// Advance `{counter}` after a successful save
+    fn bump_counter(&self) {
+        let mut settings = self.lock();
+        settings.counter += 1;
+        if let Err(e) = self.persist(&settings) {
+            println!("{}", e);
+        }
+    }
+
+    /// Full target path for a capture under the current settings
+    pub fn target_path(
+        &self,
+        metadata: &CaptureMetadata,
+        width: u32,
+        height: u32,
+        ext: &str,
+    ) -> Result<PathBuf, String> {
+        let settings = self.settings();
+        let base = match settings.base_dir {
+            Some(dir) => dir,
+            None => file_ops::get_pictures_dir()?,
+        };
+        let relative = render_template(
+            &settings.template,
+            &TemplateContext {
+                metadata,
+                width,
+                height,
+                counter: settings.counter,
+                ext,
+            },
+        )?;
+        Ok(PathBuf::from(base).join(relative))
+    }
+
+    /// Save a capture under its templated name
+    /// `overwrite` confirms a conflict previously reported for the `Ask` policy
+    pub fn save(
+        &self,
+        image: &image::RgbaImage,
+        metadata: &CaptureMetadata,
+        spec: &ExportSpec,
+        policy: CollisionPolicy,
+        overwrite: bool,
+    ) -> Result<NamedSave, String> {
+        let target = self.target_path(metadata, image.width(), image.height(), spec.extension())?;
+        let policy = if overwrite { CollisionPolicy::Overwrite } else { policy };
+        let resolved = resolve_collision(target, policy)?;
+        if resolved.conflict {
+            return Ok(NamedSave {
+                result: None,
+                conflict: Some(resolved.path),
+            });
+        }
+
+        let options = ExportOptions {
+            embed: self.settings().embed_metadata.then(EmbedOptions::default),
+            ..ExportOptions::default()
+        };
+        let result = export::export(image, spec, &resolved.path, &options, Some(metadata))?;
+        self.bump_counter();
+        Ok(NamedSave {
+            result: Some(result),
+            conflict: None,
+        })
+    }
+}
+
+/// Save a new capture to disk in the background when auto-save is enabled
+/// Auto-save cannot ask, so the `Ask` policy falls back to incrementing
+pub fn auto_save(app: &AppHandle, capture_ref: &CaptureRef) {
+    let settings = app.state::<NamingStore>().settings();
+    if !settings.auto_save {
+        return;
+    }
+
+    let store = app.state::<CaptureStore>();
+    let (Some(image), Some(metadata)) = (store.get(&capture_ref.id), store.metadata(&capture_ref.id))
+    else {
+        return;
+    };
+
+    let policy = match settings.collision {
+        CollisionPolicy::Ask => CollisionPolicy::Increment,
+        other => other,
+    };
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let naming = app.state::<NamingStore>();
+        match naming.save(&image, &metadata, &settings.auto_save_format, policy, false) {
+            Ok(NamedSave { result: Some(result), .. }) => println!("Auto-saved capture: {}", result.path),
+            Ok(_) => {}
+            Err(e) => println!("Failed to auto-save capture: {}", e),
+        }
+    });
+}
+
+/// Get naming and auto-save settings
+#[tauri::command]
+pub fn get_naming_settings(naming: State<'_, NamingStore>) -> NamingSettings {
+    naming.settings()
+}
+
+/// Update naming and auto-save settings (the template is validated first)
+#[tauri::command]
+pub fn update_naming_settings(naming: State<'_, NamingStore>, settings: NamingSettings) -> Result<(), String> {
+    naming.set_settings(settings)
+}
+
+/// Preview where a capture would be saved, without writing anything
+#[tauri::command]
+pub fn preview_capture_path(
+    store: State<'_, CaptureStore>,
+    naming: State<'_, NamingStore>,
+    capture_id: String,
+    spec: ExportSpec,
+) -> Result<ResolvedPath, String> {
+    let image = store.get(&capture_id).ok_or("Capture not found")?;
+    let metadata = store.metadata(&capture_id).ok_or("Capture not found")?;
+    let target = naming.target_path(&metadata, image.width(), image.height(), spec.extension())?;
+    resolve_collision(target, naming.settings().collision)
+}
+
+/// Save a stored capture under its templated name
+/// Returns a conflict instead of writing when the policy is `Ask` and the file exists;
+/// call again with `overwrite: true` once the user confirms
+#[tauri::command]
+pub async fn save_capture_named(
+    store: State<'_, CaptureStore>,
+    naming: State<'_, NamingStore>,
+    capture_id: String,
+    spec: ExportSpec,
+    overwrite: Option<bool>,
+) -> Result<NamedSave, String> {
+    let image = store.get(&capture_id).ok_or("Capture not found")?;
+    let metadata = store.metadata(&capture_id).ok_or("Capture not found")?;
+    let policy = naming.settings().collision;
+    naming.save(&image, &metadata, &spec, policy, overwrite.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture_store::CaptureKind;
+
+    fn metadata() -> CaptureMetadata {
+        let mut metadata = CaptureMetadata::new(CaptureKind::Window, "fake");
+        metadata.app_name = Some("Firefox".to_string());
+        metadata.window_title = Some("Bug report".to_string());
+        metadata.monitor_name = Some("DELL".to_string());
+        metadata.captured_at = Local
+            .with_ymd_and_hms(2026, 3, 14, 9, 26, 53)
+            .unwrap()
+            .timestamp_millis() as u64;
+        metadata
+    }
+
+    fn render(template: &str, metadata: &CaptureMetadata) -> Result<PathBuf, String> {
+        let ctx = TemplateContext { metadata, width: 640, height: 480, counter: 7, ext: "png" };
+        render_template(template, &ctx)
+    }
+
+    /// Empty scratch folder unique to one test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("beautyshot-naming-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn renders_default_template() {
+        let path = render(DEFAULT_TEMPLATE, &metadata()).unwrap();
+        assert_eq!(path, Path::new("2026-03-14").join("Firefox-Bug report-7.png"));
+    }
+
+    #[test]
+    fn renders_tokens_with_arguments() {
+        let path = render("{kind}_{width}x{height}_{time:%H%M}_{counter:04}.{ext}", &metadata()).unwrap();
+        assert_eq!(path, PathBuf::from("window_640x480_0926_0007.png"));
+    }
+
+    #[test]
+    fn empty_tokens_collapse_separators() {
+        let mut metadata = metadata();
+        metadata.app_name = None;
+        metadata.window_title = None;
+        // {app} falls back to the monitor name
+        assert_eq!(render("{app}-{title}-{counter}.{ext}", &metadata).unwrap(), PathBuf::from("DELL-7.png"));
+    }
+
+    #[test]
+    fn token_values_never_create_folders() {
+        let mut metadata = metadata();
+        metadata.window_title = Some("docs/README".to_string());
+        assert_eq!(render("{title}.{ext}", &metadata).unwrap(), PathBuf::from("docs_README.png"));
+    }
+
+    #[test]
+    fn trailing_ellipsis_title_can_be_written() {
+        let mut metadata = metadata();
+        metadata.window_title = Some("Loading...".to_string());
+        let relative = render(DEFAULT_TEMPLATE, &metadata).unwrap();
+        assert_eq!(relative, Path::new("2026-03-14").join("Firefox-Loading_-7.png"));
+
+        let dir = scratch_dir("ellipsis");
+        let path = dir.join(&relative);
+        file_ops::write_file(&path.to_string_lossy(), b"png").unwrap();
+        assert!(path.is_file());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_templates() {
+        let metadata = metadata();
+        assert!(render("{nope}.png", &metadata).is_err());
+        assert!(render("{app.png", &metadata).is_err());
+        assert!(render("{counter:x}.png", &metadata).is_err());
+        assert!(render("/ /", &metadata).is_err());
+    }
+
+    #[test]
+    fn sanitizes_components() {
+        assert_eq!(sanitize_component("a\tb.png"), "a_b.png");
+        assert_eq!(sanitize_component("a/b"), "a_b");
+        assert_eq!(sanitize_component(".."), "_");
+        assert_eq!(sanitize_component("shot."), "shot");
+        assert_eq!(sanitize_component("Save As....png"), "Save As.png");
+        assert_eq!(sanitize_component("a..b...c.png"), "a_b_c.png");
+        assert_eq!(sanitize_component("-- shot --.png"), "shot.png");
+    }
+
+    #[test]
+    fn truncation_keeps_extension() {
+        let name = sanitize_component(&format!("{}.png", "é".repeat(150)));
+        assert!(name.len() <= MAX_COMPONENT_LEN);
+        assert!(name.ends_with("é.png"));
+    }
+
+    #[test]
+    fn collision_policies() {
+        let dir = scratch_dir("collision");
+        let target = dir.join("shot.png");
+
+        let free = resolve_collision(target.clone(), CollisionPolicy::Ask).unwrap();
+        assert_eq!((free.path.as_str(), free.conflict), (target.to_str().unwrap(), false));
+
+        fs::write(&target, b"1").unwrap();
+        let next = resolve_collision(target.clone(), CollisionPolicy::Increment).unwrap();
+        assert_eq!(PathBuf::from(&next.path), dir.join("shot-2.png"));
+        fs::write(&next.path, b"2").unwrap();
+        let next = resolve_collision(target.clone(), CollisionPolicy::Increment).unwrap();
+        assert_eq!(PathBuf::from(&next.path), dir.join("shot-3.png"));
+
+        let overwrite = resolve_collision(target.clone(), CollisionPolicy::Overwrite).unwrap();
+        assert_eq!((PathBuf::from(&overwrite.path), overwrite.conflict), (target.clone(), false));
+        assert!(resolve_collision(target, CollisionPolicy::Ask).unwrap().conflict);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,21 +1,33 @@
-// Overlay window management for region selection
+// Overlay window management for region selection and window picking
 // Screenshot is captured after frontend hides main window (same timing as fullscreen)
+// In window-picking mode the overlay also gets the window geometry taken with the
+// screenshot, so it can highlight the window under the mouse
+// The desktop overlay puts one window on every monitor over a composed image of
+// the whole desktop; the windows share one selection that can span monitors
 
 use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
 
-use base64::{engine::general_purpose::STANDARD, Engine};
-use image::codecs::png::{CompressionType, FilterType, PngEncoder};
-use image::ImageEncoder;
 use serde::{Deserialize, Serialize};
 use xcap::Monitor;
 
-// Store screenshot data for overlay background
-static OVERLAY_SCREENSHOT: Mutex<Option<String>> = Mutex::new(None);
+use crate::{capture_backend, history, naming};
+use crate::capture_store::{CaptureKind, CaptureRef, CaptureStore};
+use crate::screenshot::{crop_region, image_to_base64_png, monitor_metadata};
+use crate::virtual_desktop::{self, DesktopCaptureOptions, DesktopLayout};
+use crate::window_match;
+
 // Store monitor info for the current overlay
 static OVERLAY_MONITOR: Mutex<Option<OverlayMonitorInfo>> = Mutex::new(None);
+// Window geometry for the current overlay; `None` in region selection mode
+static OVERLAY_WINDOWS: Mutex<Option<Vec<OverlayWindowInfo>>> = Mutex::new(None);
+// Composed desktop image and layout for the current desktop overlay
+static DESKTOP_OVERLAY: Mutex<Option<DesktopOverlay>> = Mutex::new(None);
+
+/// Label prefix of the per-monitor desktop overlay windows (suffix is the monitor id)
+const DESKTOP_OVERLAY_PREFIX: &str = "desktop-overlay-";
 
 /// Monitor info for overlay positioning
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +39,68 @@ pub struct OverlayMonitorInfo {
     pub y: i32,
 }
 
+/// Desktop image shared by all desktop overlay windows
+#[derive(Debug, Clone, Serialize)]
+pub struct DesktopOverlay {
+    pub capture: CaptureRef,
+    pub backend: String,
+    pub layout: DesktopLayout,
+}
+
+/// A pickable window, positioned relative to the overlay monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayWindowInfo {
+    pub id: u32,
+    pub app_name: String,
+    pub title: String,
+    /// Offset from the monitor origin, in the same units as `OverlayMonitorInfo`
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Stacking order, 0 = topmost
+    pub z: u32,
+}
+
+/// Windows of other apps overlapping the monitor, translated to monitor-relative coordinates
+fn windows_on_monitor(monitor: &OverlayMonitorInfo) -> Vec<OverlayWindowInfo> {
+    let windows = match window_match::other_app_windows() {
+        Ok(windows) => windows,
+        Err(e) => {
+            println!("Failed to list windows for picker: {}", e);
+            return Vec::new();
+        }
+    };
+
+    windows
+        .into_iter()
+        .enumerate()
+        .map(|(z, w)| OverlayWindowInfo {
+            id: w.id,
+            app_name: w.app_name,
+            title: w.title,
+            x: w.x - monitor.x,
+            y: w.y - monitor.y,
+            width: w.width,
+            height: w.height,
+            z: z as u32,
+        })
+        .filter(|w| {
+            w.x < monitor.width as i32
+                && w.y < monitor.height as i32
+                && w.x + w.width as i32 > 0
+                && w.y + w.height as i32 > 0
+        })
+        .collect()
+}
+
+fn set_overlay_windows(windows: Option<Vec<OverlayWindowInfo>>) {
+    let mut data = OVERLAY_WINDOWS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *data = windows;
+}
+
 /// Wait for Windows DWM animation to complete
 #[cfg(target_os = "windows")]
 fn wait_for_dwm_animation() {
@@ -38,79 +112,38 @@ fn wait_for_dwm_animation() {
     }
 }
 
-
-
-/// Capture specific monitor screenshot and convert to base64
-fn capture_monitor_for_overlay(monitor_id: u32) -> Result<String, String> {
-    let monitors = Monitor::all().map_err(|e| e.to_string())?;
-    let monitor = monitors
-        .into_iter()
-        .find(|m| m.id().unwrap_or(0) == monitor_id)
-        .ok_or("Monitor not found")?;
-
-    capture_monitor_to_base64(&monitor)
-}
-
-/// Capture a monitor and encode as base64 PNG
-fn capture_monitor_to_base64(monitor: &Monitor) -> Result<String, String> {
-    let image = monitor.capture_image().map_err(|e| e.to_string())?;
-
-    let width = image.width();
-    let height = image.height();
-
-    if width == 0 || height == 0 {
-        return Err("Screen recording permission not granted".to_string());
-    }
-
-    // Fast PNG encoding
-    let estimated_size = (width * height * 4) as usize + 1024;
-    let mut bytes: Vec<u8> = Vec::with_capacity(estimated_size);
-    let encoder =
-        PngEncoder::new_with_quality(&mut bytes, CompressionType::Fast, FilterType::NoFilter);
-    encoder
-        .write_image(
-            image.as_raw(),
-            width,
-            height,
-            image::ExtendedColorType::Rgba8,
-        )
-        .map_err(|e| e.to_string())?;
-
-    Ok(STANDARD.encode(&bytes))
+/// Script giving a newly created overlay window its background capture
+/// The window may load after "overlay-activate" was emitted, so it reads this on mount
+fn overlay_capture_script(capture_ref: &CaptureRef) -> String {
+    let json = serde_json::to_string(capture_ref).unwrap_or_else(|_| "null".to_string());
+    format!("window.__OVERLAY_CAPTURE__ = {};", json)
 }
 
-/// Get stored screenshot data
+/// Get stored screenshot data as base64 PNG (compatibility path)
+/// Prefer the capture:// protocol with the capture id to avoid base64
 #[tauri::command]
-pub fn get_screenshot_data() -> Option<String> {
-    let data = OVERLAY_SCREENSHOT
-        .lock()
-        .unwrap_or_else(|poisoned| poisoned.into_inner());
-    data.clone()
+pub fn get_screenshot_data(store: State<'_, CaptureStore>, capture_id: String) -> Option<String> {
+    let image = store.get(&capture_id)?;
+    image_to_base64_png(&image).ok()
 }
 
 /// Clear stored screenshot data
 #[tauri::command]
-pub fn clear_screenshot_data() {
-    let mut data = OVERLAY_SCREENSHOT
-        .lock()
-        .unwrap_or_else(|poisoned| poisoned.into_inner());
-    *data = None;
+pub fn clear_screenshot_data(store: State<'_, CaptureStore>, capture_id: String) {
+    store.release(&capture_id);
 }
 
 /// Capture screenshot and show overlay in one call (for speed)
 /// Frontend already hid main window and waited for DWM
 #[tauri::command]
 pub async fn capture_and_show_overlay(app: AppHandle) -> Result<(), String> {
-    // Capture screenshot using same function as fullscreen
-    let screenshot_base64 = crate::screenshot::capture_fullscreen()?;
+    // Capture screenshot using same backend chain as fullscreen
+    let capture = capture_backend::registry().capture_monitor(None)?;
+    let metadata = monitor_metadata(CaptureKind::Monitor, None, capture.backend);
 
-    // Store screenshot
-    {
-        let mut data = OVERLAY_SCREENSHOT
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        *data = Some(screenshot_base64);
-    }
+    // Store screenshot; its id travels with "overlay-activate" and "region-selected"
+    let capture_ref = app.state::<CaptureStore>().insert(capture.image, metadata);
+    set_overlay_windows(None);
 
     // Get or create overlay window (always starts hidden)
     let window = match app.get_webview_window("region-overlay") {
@@ -133,19 +166,17 @@ pub async fn capture_and_show_overlay(app: AppHandle) -> Result<(), String> {
             .closable(true)
             .resizable(false)
             .visible(false)
+            .initialization_script(overlay_capture_script(&capture_ref))
             .build()
             .map_err(|e| {
-                let mut data = OVERLAY_SCREENSHOT
-                    .lock()
-                    .unwrap_or_else(|poisoned| poisoned.into_inner());
-                *data = None;
+                app.state::<CaptureStore>().release(&capture_ref.id);
                 format!("{}", e)
             })?
         }
     };
 
     let _ = window.set_fullscreen(true);
-    let _ = window.emit("overlay-activate", ());
+    let _ = window.emit("overlay-activate", &capture_ref);
 
     Ok(())
 }
@@ -163,6 +194,7 @@ pub async fn hide_overlay_window(app: AppHandle) -> Result<(), String> {
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         *data = None;
     }
+    set_overlay_windows(None);
     Ok(())
 }
 
@@ -171,6 +203,24 @@ pub async fn hide_overlay_window(app: AppHandle) -> Result<(), String> {
 pub async fn show_overlay_window_on_monitor(
     app: AppHandle,
     monitor_id: u32,
+) -> Result<(), String> {
+    show_overlay_on_monitor(app, monitor_id, false)
+}
+
+/// Show overlay window on a specific monitor for picking a window
+/// The overlay reports the clicked window id through "window-selected"
+#[tauri::command]
+pub async fn show_window_picker_on_monitor(
+    app: AppHandle,
+    monitor_id: u32,
+) -> Result<(), String> {
+    show_overlay_on_monitor(app, monitor_id, true)
+}
+
+fn show_overlay_on_monitor(
+    app: AppHandle,
+    monitor_id: u32,
+    pick_windows: bool,
 ) -> Result<(), String> {
     // Get monitor info first
     let monitors = Monitor::all().map_err(|e| e.to_string())?;
@@ -200,21 +250,21 @@ pub async fn show_overlay_window_on_monitor(
     thread::sleep(Duration::from_millis(50));
 
     // Capture screenshot from specific monitor
-    let screenshot_base64 = capture_monitor_for_overlay(monitor_id)?;
+    let capture = capture_backend::registry().capture_monitor(Some(monitor_id))?;
+    let metadata = monitor_metadata(CaptureKind::Monitor, Some(monitor_id), capture.backend);
+
+    // Window geometry is read right after the screenshot so the highlight matches it
+    let windows = pick_windows.then(|| windows_on_monitor(&monitor_info));
 
     // Store screenshot and monitor info
-    {
-        let mut data = OVERLAY_SCREENSHOT
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        *data = Some(screenshot_base64);
-    }
+    let capture_ref = app.state::<CaptureStore>().insert(capture.image, metadata);
     {
         let mut data = OVERLAY_MONITOR
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         *data = Some(monitor_info.clone());
     }
+    set_overlay_windows(windows.clone());
 
     // Close existing overlay if any (to recreate with new position)
     if let Some(existing) = app.get_webview_window("region-overlay") {
@@ -239,21 +289,23 @@ pub async fn show_overlay_window_on_monitor(
     .closable(true)
     .resizable(false)
     .visible(true)
+    .initialization_script(overlay_capture_script(&capture_ref))
     .build()
     .map_err(|e| {
         // Clear data on failure
-        let mut screenshot = OVERLAY_SCREENSHOT
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        *screenshot = None;
+        app.state::<CaptureStore>().release(&capture_ref.id);
         let mut monitor = OVERLAY_MONITOR
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         *monitor = None;
+        set_overlay_windows(None);
         format!("{}", e)
     })?;
 
-    let _ = window.emit("overlay-activate", ());
+    let _ = window.emit("overlay-activate", &capture_ref);
+    if let Some(windows) = &windows {
+        let _ = window.emit("overlay-windows", windows);
+    }
 
     Ok(())
 }
@@ -267,6 +319,157 @@ pub fn get_overlay_monitor() -> Option<OverlayMonitorInfo> {
     data.clone()
 }
 
+/// Get the pickable windows for the current overlay (`None` in region selection mode)
+#[tauri::command]
+pub fn get_overlay_windows() -> Option<Vec<OverlayWindowInfo>> {
+    let data = OVERLAY_WINDOWS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    data.clone()
+}
+
+fn take_desktop_overlay() -> Option<DesktopOverlay> {
+    let mut data = DESKTOP_OVERLAY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    data.take()
+}
+
+/// Close every desktop overlay window
+fn close_desktop_overlay_windows(app: &AppHandle) {
+    for (label, window) in app.webview_windows() {
+        if label.starts_with(DESKTOP_OVERLAY_PREFIX) {
+            let _ = window.close();
+        }
+    }
+}
+
+/// Capture the whole desktop and show a selection overlay on every monitor
+/// Overlays show themselves once their part of the desktop image has loaded
+#[tauri::command]
+pub async fn show_desktop_overlay(app: AppHandle) -> Result<(), String> {
+    // Hide main window first
+    if let Some(main_window) = app.get_webview_window("main") {
+        let _ = main_window.hide();
+    }
+
+    // Wait for window hide animation
+    #[cfg(target_os = "windows")]
+    wait_for_dwm_animation();
+
+    #[cfg(not(target_os = "windows"))]
+    thread::sleep(Duration::from_millis(50));
+
+    let desktop = virtual_desktop::capture_desktop(&DesktopCaptureOptions::default())?;
+    let metadata =
+        virtual_desktop::desktop_metadata(CaptureKind::Monitor, desktop.backend, &desktop.layout);
+    let capture_ref = app.state::<CaptureStore>().insert(desktop.image, metadata);
+
+    // Drop any previous session (its capture is no longer needed)
+    if let Some(previous) = take_desktop_overlay() {
+        app.state::<CaptureStore>().release(&previous.capture.id);
+    }
+    close_desktop_overlay_windows(&app);
+
+    {
+        let mut data = DESKTOP_OVERLAY
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *data = Some(DesktopOverlay {
+            capture: capture_ref.clone(),
+            backend: desktop.backend.to_string(),
+            layout: desktop.layout.clone(),
+        });
+    }
+
+    for monitor in &desktop.layout.monitors {
+        let window = WebviewWindowBuilder::new(
+            &app,
+            format!("{}{}", DESKTOP_OVERLAY_PREFIX, monitor.id),
+            WebviewUrl::App("overlay.html".into()),
+        )
+        .title("")
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .closable(true)
+        .resizable(false)
+        .visible(false)
+        .build()
+        .map_err(|e| {
+            close_desktop_overlay_windows(&app);
+            if let Some(overlay) = take_desktop_overlay() {
+                app.state::<CaptureStore>().release(&overlay.capture.id);
+            }
+            format!("{}", e)
+        })?;
+
+        // Monitor geometry is in points on macOS and physical pixels elsewhere
+        #[cfg(target_os = "macos")]
+        {
+            let _ = window.set_position(tauri::LogicalPosition::new(monitor.x, monitor.y));
+            let _ = window.set_size(tauri::LogicalSize::new(monitor.width, monitor.height));
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = window.set_position(tauri::PhysicalPosition::new(monitor.x, monitor.y));
+            let _ = window.set_size(tauri::PhysicalSize::new(monitor.width, monitor.height));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the desktop image and layout for the current desktop overlay
+#[tauri::command]
+pub fn get_desktop_overlay() -> Option<DesktopOverlay> {
+    let data = DESKTOP_OVERLAY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    data.clone()
+}
+
+/// Close all desktop overlay windows
+/// With a region (canvas pixels) it is cut from the desktop image into the capture store;
+/// without one the selection was cancelled. The desktop image is released either way
+#[tauri::command]
+pub async fn finish_desktop_overlay(
+    app: AppHandle,
+    x: Option<i32>,
+    y: Option<i32>,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<Option<CaptureRef>, String> {
+    close_desktop_overlay_windows(&app);
+    let Some(overlay) = take_desktop_overlay() else {
+        return Ok(None);
+    };
+
+    let store = app.state::<CaptureStore>();
+    let result = match (x, y, width, height) {
+        (Some(x), Some(y), Some(width), Some(height)) => {
+            let cropped = store
+                .get(&overlay.capture.id)
+                .ok_or("Desktop capture no longer available".to_string())
+                .and_then(|image| crop_region(&image, x, y, width, height));
+            cropped.map(|image| {
+                let metadata = virtual_desktop::desktop_metadata(
+                    CaptureKind::Region,
+                    &overlay.backend,
+                    &overlay.layout,
+                );
+                let capture_ref = store.insert(image, metadata);
+                history::auto_record(&app, &capture_ref);
+                naming::auto_save(&app, &capture_ref);
+                Some(capture_ref)
+            })
+        }
+        _ => Ok(None),
+    };
+    store.release(&overlay.capture.id);
+    result
+}
+
 // Compatibility aliases for existing code
 #[tauri::command]
 pub async fn create_overlay_window(app: AppHandle) -> Result<(), String> {
@@ -0,0 +1,307 @@
+// Lossless PNG optimization
+// Re-encodes pixels in the smallest exact representation: palette when there are
+// at most 256 colors, grayscale or RGB when alpha/color channels are redundant,
+// maximum deflate and the better of adaptive/no filtering. Only image data is
+// written, so ancillary chunks (text, EXIF, color profiles) are stripped.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use image::RgbaImage;
+use serde::Serialize;
+
+use crate::file_ops;
+
+/// Largest PNG file accepted for in-place optimization (matches file_ops limit)
+const MAX_INPUT_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Filters tried for each candidate encoding; the smallest output wins
+const FILTERS: [png::Filter; 2] = [png::Filter::Adaptive, png::Filter::NoFilter];
+
+/// Size before and after optimization
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizeReport {
+    pub original_size: usize,
+    pub optimized_size: usize,
+    pub bytes_saved: usize,
+    /// Chosen representation, e.g. "palette-4bit", "rgb", "rgba"
+    pub encoding: String,
+}
+
+impl OptimizeReport {
+    pub fn new(original_size: usize, optimized_size: usize, encoding: String) -> Self {
+        Self {
+            original_size,
+            optimized_size,
+            bytes_saved: original_size.saturating_sub(optimized_size),
+            encoding,
+        }
+    }
+}
+
+/// Pixel data in a reduced PNG color type
+struct Reduced {
+    color: png::ColorType,
+    depth: png::BitDepth,
+    data: Vec<u8>,
+    palette: Option<Vec<u8>>,
+    trns: Option<Vec<u8>>,
+    label: String,
+}
+
+/// Build a palette if the image has at most 256 distinct colors
+/// Entries with transparency come first so the tRNS chunk stays short
+fn build_palette(image: &RgbaImage) -> Option<Vec<[u8; 4]>> {
+    let mut seen: HashMap<[u8; 4], ()> = HashMap::new();
+    for pixel in image.pixels() {
+        seen.insert(pixel.0, ());
+        if seen.len() > 256 {
+            return None;
+        }
+    }
+    let mut colors: Vec<[u8; 4]> = seen.into_keys().collect();
+    colors.sort_by_key(|c| (c[3] == 255, *c));
+    Some(colors)
+}
+
+fn bit_depth_for(colors: usize) -> (png::BitDepth, usize) {
+    match colors {
+        0..=2 => (png::BitDepth::One, 1),
+        3..=4 => (png::BitDepth::Two, 2),
+        5..=16 => (png::BitDepth::Four, 4),
+        _ => (png::BitDepth::Eight, 8),
+    }
+}
+
+/// Pack palette indices into rows at the given bit depth
+fn pack_indices(image: &RgbaImage, index: &HashMap<[u8; 4], u8>, bits: usize) -> Vec<u8> {
+    let width = image.width() as usize;
+    let row_bytes = (width * bits).div_ceil(8);
+    let mut data = vec![0u8; row_bytes * image.height() as usize];
+
+    for (y, row) in image.rows().enumerate() {
+        let out = &mut data[y * row_bytes..(y + 1) * row_bytes];
+        for (x, pixel) in row.enumerate() {
+            let value = index[&pixel.0];
+            let bit = x * bits;
+            // Pixels are packed from the most significant bit
+            let shift = 8 - bits - (bit % 8);
+            out[bit / 8] |= value << shift;
+        }
+    }
+    data
+}
+
+fn reduce(image: &RgbaImage) -> Reduced {
+    let opaque = image.pixels().all(|p| p.0[3] == 255);
+
+    if let Some(palette) = build_palette(image) {
+        let (depth, bits) = bit_depth_for(palette.len());
+        let index: HashMap<[u8; 4], u8> = palette
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (*c, i as u8))
+            .collect();
+        let trns: Vec<u8> = palette
+            .iter()
+            .take_while(|c| c[3] != 255)
+            .map(|c| c[3])
+            .collect();
+        return Reduced {
+            color: png::ColorType::Indexed,
+            depth,
+            data: pack_indices(image, &index, bits),
+            palette: Some(palette.iter().flat_map(|c| [c[0], c[1], c[2]]).collect()),
+            trns: (!trns.is_empty()).then_some(trns),
+            label: format!("palette-{}bit", bits),
+        };
+    }
+
+    let gray = image.pixels().all(|p| p.0[0] == p.0[1] && p.0[1] == p.0[2]);
+    let raw = image.as_raw();
+    let (color, data, label) = match (gray, opaque) {
+        (true, true) => (png::ColorType::Grayscale, raw.chunks_exact(4).map(|p| p[0]).collect(), "gray"),
+        (true, false) => (
+            png::ColorType::GrayscaleAlpha,
+            raw.chunks_exact(4).flat_map(|p| [p[0], p[3]]).collect(),
+            "gray-alpha",
+        ),
+        (false, true) => (
+            png::ColorType::Rgb,
+            raw.chunks_exact(4).flat_map(|p| [p[0], p[1], p[2]]).collect(),
+            "rgb",
+        ),
+        (false, false) => (png::ColorType::Rgba, raw.clone(), "rgba"),
+    };
+    Reduced {
+        color,
+        depth: png::BitDepth::Eight,
+        data,
+        palette: None,
+        trns: None,
+        label: label.to_string(),
+    }
+}
+
+fn encode_reduced(reduced: &Reduced, width: u32, height: u32, filter: png::Filter) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(reduced.color);
+        encoder.set_depth(reduced.depth);
+        encoder.set_deflate_compression(png::DeflateCompression::Level(9));
+        encoder.set_filter(filter);
+        if let Some(palette) = &reduced.palette {
+            encoder.set_palette(palette.clone());
+        }
+        if let Some(trns) = &reduced.trns {
+            encoder.set_trns(trns.clone());
+        }
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+        writer
+            .write_image_data(&reduced.data)
+            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+        writer.finish().map_err(|e| e.to_string())?;
+    }
+    Ok(bytes)
+}
+
+/// Encode an image as the smallest lossless PNG we can produce
+/// Returns the bytes and a label for the chosen representation
+pub fn optimize(image: &RgbaImage) -> Result<(Vec<u8>, String), String> {
+    let reduced = reduce(image);
+    let mut best: Option<Vec<u8>> = None;
+    for filter in FILTERS {
+        let bytes = encode_reduced(&reduced, image.width(), image.height(), filter)?;
+        if best.as_ref().is_none_or(|b| bytes.len() < b.len()) {
+            best = Some(bytes);
+        }
+    }
+    let best = best.ok_or("No PNG encoding produced")?;
+    Ok((best, reduced.label))
+}
+
+/// Optimize a PNG file in place; the file is only rewritten if it gets smaller
+pub fn optimize_file(path: &str) -> Result<OptimizeReport, String> {
+    let size = std::fs::metadata(Path::new(path))
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?
+        .len();
+    if size > MAX_INPUT_SIZE {
+        return Err("PNG file is too large to optimize".to_string());
+    }
+    let original = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let decoded = image::load_from_memory_with_format(&original, image::ImageFormat::Png)
+        .map_err(|e| format!("Invalid PNG: {}", e))?;
+    // 16-bit images would lose precision when reduced to 8-bit RGBA
+    if decoded.color().bytes_per_pixel() > decoded.color().channel_count() {
+        return Err("Only 8-bit PNGs can be optimized losslessly".to_string());
+    }
+
+    let (optimized, encoding) = optimize(&decoded.to_rgba8())?;
+    if optimized.len() < original.len() {
+        file_ops::write_file(path, &optimized)?;
+        Ok(OptimizeReport::new(original.len(), optimized.len(), encoding))
+    } else {
+        Ok(OptimizeReport::new(original.len(), original.len(), "unchanged".to_string()))
+    }
+}
+
+/// Losslessly shrink a saved PNG file, reporting bytes saved
+#[tauri::command]
+pub async fn optimize_png_file(path: String) -> Result<OptimizeReport, String> {
+    optimize_file(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use image::Rgba;
+
+    /// Optimize, then check the label and that decoding gives the exact pixels back
+    fn assert_lossless(image: &RgbaImage, label: &str) {
+        let (bytes, encoding) = optimize(image).unwrap();
+        assert_eq!(encoding, label);
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).unwrap();
+        assert_eq!(&decoded.to_rgba8(), image);
+    }
+
+    /// Odd width so packed rows end mid-byte
+    fn with_colors(colors: u32) -> RgbaImage {
+        RgbaImage::from_fn(13, 7, |x, y| {
+            let i = (x + y * 13) % colors;
+            Rgba([(i * 37) as u8, (i * 11) as u8, i as u8, 255])
+        })
+    }
+
+    /// Scratch file path unique to one test
+    fn scratch_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("beautyshot-optimize-{}-{}.png", name, std::process::id()))
+    }
+
+    #[test]
+    fn palette_bit_depth_follows_color_count() {
+        assert_lossless(&with_colors(2), "palette-1bit");
+        assert_lossless(&with_colors(4), "palette-2bit");
+        assert_lossless(&with_colors(16), "palette-4bit");
+        assert_lossless(&with_colors(91), "palette-8bit");
+    }
+
+    #[test]
+    fn palette_keeps_transparency() {
+        let image = RgbaImage::from_fn(9, 5, |x, _| match x % 3 {
+            0 => Rgba([0, 0, 0, 0]),
+            1 => Rgba([255, 0, 0, 128]),
+            _ => Rgba([0, 0, 255, 255]),
+        });
+        assert_lossless(&image, "palette-2bit");
+    }
+
+    #[test]
+    fn many_colors_use_direct_color_types() {
+        let rgb = RgbaImage::from_fn(32, 32, |x, y| Rgba([(x * 8) as u8, (y * 8) as u8, 7, 255]));
+        assert_lossless(&rgb, "rgb");
+
+        let rgba = RgbaImage::from_fn(32, 32, |x, y| Rgba([(x * 8) as u8, 7, 7, (y * 8) as u8]));
+        assert_lossless(&rgba, "rgba");
+
+        let gray_alpha = RgbaImage::from_fn(32, 32, |x, y| {
+            let v = (x * 8) as u8;
+            Rgba([v, v, v, (y * 8) as u8])
+        });
+        assert_lossless(&gray_alpha, "gray-alpha");
+    }
+
+    #[test]
+    fn optimizes_file_in_place() {
+        let path = scratch_file("in-place");
+        let image = with_colors(4);
+        image::save_buffer(&path, image.as_raw(), 13, 7, image::ExtendedColorType::Rgba8).unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let report = optimize_file(path_str).unwrap();
+        assert_eq!(report.encoding, "palette-2bit");
+        assert!(report.bytes_saved > 0);
+        assert_eq!(std::fs::metadata(&path).unwrap().len() as usize, report.optimized_size);
+        assert_eq!(image::open(&path).unwrap().to_rgba8(), image);
+
+        // Already optimal: the file is left alone
+        let again = optimize_file(path_str).unwrap();
+        assert_eq!((again.encoding.as_str(), again.bytes_saved), ("unchanged", 0));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_16_bit_files() {
+        let path = scratch_file("16bit");
+        let image = image::ImageBuffer::<image::Rgb<u16>, _>::from_pixel(4, 4, image::Rgb([1000, 2, 3]));
+        image.save(&path).unwrap();
+        assert!(optimize_file(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
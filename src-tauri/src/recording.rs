@@ -0,0 +1,425 @@
+// Animated GIF / APNG recording of a monitor region
+// A background thread grabs the region at a fixed frame rate (same capture path as
+// capture_region_from_monitor), merges identical consecutive frames, then encodes
+
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::screenshot::crop_region;
+use crate::{capture_backend, file_ops, tray};
+
+/// Frame rate bounds
+const MAX_FPS: u32 = 30;
+
+/// Longest recording allowed
+const MAX_DURATION_SECS: u32 = 120;
+
+/// Raw frame memory budget; recording stops early when exceeded
+const MAX_FRAME_BYTES: usize = 1024 * 1024 * 1024;
+
+/// Largest recording file written; bounded by the raw frame budget, so encoding never overshoots it
+const MAX_FILE_BYTES: usize = 2 * MAX_FRAME_BYTES;
+
+/// Consecutive failed captures after which recording stops and keeps what it has
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+/// Stop flag of the running recording, if any
+static ACTIVE_RECORDING: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// Output container for a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    Gif,
+    Apng,
+}
+
+impl RecordingFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            RecordingFormat::Gif => "gif",
+            RecordingFormat::Apng => "png",
+        }
+    }
+}
+
+fn default_fps() -> u32 {
+    10
+}
+
+fn default_max_duration() -> u32 {
+    30
+}
+
+fn default_quantize_speed() -> i32 {
+    10
+}
+
+/// Region, timing and palette options for a recording
+/// Coordinates are relative to the monitor's origin
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordingOptions {
+    pub monitor_id: u32,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub format: RecordingFormat,
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+    #[serde(default = "default_max_duration")]
+    pub max_duration_secs: u32,
+    /// GIF palette size (2-256); fewer colors give smaller files
+    pub max_colors: Option<u16>,
+    /// NeuQuant speed for GIF palettes, 1 (best) to 30 (fastest)
+    #[serde(default = "default_quantize_speed")]
+    pub quantize_speed: i32,
+    /// Destination file (Pictures/BeautyShot/recording-<time>.<ext> when omitted)
+    pub output_path: Option<String>,
+}
+
+impl RecordingOptions {
+    fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err("Recording region is empty".to_string());
+        }
+        if !(1..=MAX_FPS).contains(&self.fps) {
+            return Err(format!("Frame rate must be between 1 and {}", MAX_FPS));
+        }
+        if !(1..=MAX_DURATION_SECS).contains(&self.max_duration_secs) {
+            return Err(format!("Duration must be between 1 and {} seconds", MAX_DURATION_SECS));
+        }
+        if let Some(colors) = self.max_colors {
+            if !(2..=256).contains(&colors) {
+                return Err("max_colors must be between 2 and 256".to_string());
+            }
+        }
+        if !(1..=30).contains(&self.quantize_speed) {
+            return Err("quantize_speed must be between 1 and 30".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A captured frame and how long it stays on screen
+struct RecordedFrame {
+    image: RgbaImage,
+    duration_ms: u32,
+}
+
+/// Progress payload emitted for each captured frame
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingProgress {
+    pub captured: u32,
+    pub unique: u32,
+    pub elapsed_ms: u64,
+}
+
+/// Result payload when a recording has been encoded and saved
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingResult {
+    pub path: String,
+    pub format: RecordingFormat,
+    pub frames: u32,
+    pub duration_ms: u64,
+    pub byte_size: usize,
+}
+
+/// Append a frame taken `taken_ms` after the recording started, or extend the
+/// previous one instead if the pixels are identical
+/// The previous frame stayed on screen until then, so playback keeps real time even
+/// when captures run slower than the frame rate or some of them fail
+fn push_frame(frames: &mut Vec<RecordedFrame>, image: RgbaImage, taken_ms: u32, last_taken_ms: &mut u32) {
+    let shown_ms = taken_ms.saturating_sub(std::mem::replace(last_taken_ms, taken_ms));
+    if let Some(last) = frames.last_mut() {
+        last.duration_ms += shown_ms;
+        if last.image.dimensions() == image.dimensions() && last.image.as_raw() == image.as_raw() {
+            return;
+        }
+    }
+    frames.push(RecordedFrame { image, duration_ms: 0 });
+}
+
+/// Reduce a frame to at most `colors` colors so the GIF palette is used as-is
+fn quantize(image: &mut RgbaImage, colors: usize, speed: i32) {
+    let quantizer = color_quant::NeuQuant::new(speed, colors, image.as_raw());
+    for pixel in image.pixels_mut() {
+        quantizer.map_pixel(&mut pixel.0);
+    }
+}
+
+fn encode_gif(frames: Vec<RecordedFrame>, options: &RecordingOptions) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut bytes, options.quantize_speed);
+        encoder.set_repeat(Repeat::Infinite).map_err(|e| e.to_string())?;
+
+        for mut frame in frames {
+            if let Some(colors) = options.max_colors.filter(|c| *c < 256) {
+                quantize(&mut frame.image, colors as usize, options.quantize_speed);
+            }
+            let delay = Delay::from_numer_denom_ms(frame.duration_ms, 1);
+            encoder
+                .encode_frame(Frame::from_parts(frame.image, 0, 0, delay))
+                .map_err(|e| format!("Failed to encode GIF frame: {}", e))?;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Encode frames as APNG, sized from the recorded frames
+/// (the requested region is clamped to the monitor, so frames may be smaller)
+fn encode_apng(frames: Vec<RecordedFrame>) -> Result<Vec<u8>, String> {
+    let (width, height) = frames
+        .first()
+        .map(|frame| frame.image.dimensions())
+        .ok_or_else(|| "No frames to encode".to_string())?;
+    let mut bytes = Cursor::new(Vec::new());
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .set_animated(frames.len() as u32, 0)
+            .map_err(|e| e.to_string())?;
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write APNG header: {}", e))?;
+        for frame in &frames {
+            // fcTL delays are u16 fractions; long static stretches are clamped
+            let delay = frame.duration_ms.min(u16::MAX as u32) as u16;
+            writer.set_frame_delay(delay, 1000).map_err(|e| e.to_string())?;
+            writer
+                .write_image_data(frame.image.as_raw())
+                .map_err(|e| format!("Failed to encode APNG frame: {}", e))?;
+        }
+        writer.finish().map_err(|e| e.to_string())?;
+    }
+    Ok(bytes.into_inner())
+}
+
+fn output_path(options: &RecordingOptions) -> Result<String, String> {
+    if let Some(path) = &options.output_path {
+        return Ok(path.clone());
+    }
+    let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let file_name = format!("recording-{}.{}", stamp, options.format.extension());
+    Ok(PathBuf::from(file_ops::get_pictures_dir()?)
+        .join(file_name)
+        .to_string_lossy()
+        .to_string())
+}
+
+/// Capture one frame of the region; all frames must match the first one's size
+fn capture_frame(options: &RecordingOptions, size: Option<(u32, u32)>) -> Result<RgbaImage, String> {
+    let capture = capture_backend::registry().capture_monitor(Some(options.monitor_id))?;
+    let image = crop_region(&capture.image, options.x, options.y, options.width, options.height)?;
+    // A monitor resolution change mid-recording would break the APNG frame size
+    if size.is_some_and(|size| size != image.dimensions()) {
+        return Err("Recording region changed size while recording".to_string());
+    }
+    Ok(image)
+}
+
+/// Grab frames until stopped or out of time/memory
+/// Failed captures are skipped; frames recorded so far are kept
+fn record_frames(app: &AppHandle, options: &RecordingOptions, stop: &AtomicBool) -> Result<Vec<RecordedFrame>, String> {
+    let frame_ms = 1000 / options.fps;
+    let interval = Duration::from_millis(frame_ms as u64);
+    let max_duration = Duration::from_secs(options.max_duration_secs as u64);
+    let frame_bytes = options.width as usize * options.height as usize * 4;
+
+    let started = Instant::now();
+    let elapsed_ms = || started.elapsed().as_millis() as u32;
+    let mut frames: Vec<RecordedFrame> = Vec::new();
+    let mut last_taken_ms = 0u32;
+    let mut captured = 0u32;
+    let mut failures = 0u32;
+    let mut last_error = None;
+
+    while !stop.load(Ordering::SeqCst) && started.elapsed() < max_duration {
+        let taken_ms = elapsed_ms();
+        match capture_frame(options, frames.first().map(|f| f.image.dimensions())) {
+            Ok(image) => {
+                push_frame(&mut frames, image, taken_ms, &mut last_taken_ms);
+                failures = 0;
+            }
+            Err(e) => {
+                // Skip the frame: the previous one stays on screen until the next capture
+                println!("Recording frame failed: {}", e);
+                failures += 1;
+                last_error = Some(e);
+                if failures >= MAX_CONSECUTIVE_FAILURES {
+                    println!("Too many failed frames, stopping recording");
+                    break;
+                }
+            }
+        }
+        captured += 1;
+
+        let _ = app.emit(
+            "recording-progress",
+            RecordingProgress {
+                captured,
+                unique: frames.len() as u32,
+                elapsed_ms: started.elapsed().as_millis() as u64,
+            },
+        );
+
+        if frames.len() * frame_bytes > MAX_FRAME_BYTES {
+            println!("Recording memory budget reached, stopping");
+            break;
+        }
+
+        // Keep to the frame schedule even when a capture runs long
+        let next = started + interval * captured;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        }
+    }
+
+    // The last frame lasts until the recording ended, and at least one frame slot
+    match frames.last_mut() {
+        Some(last) => last.duration_ms += elapsed_ms().saturating_sub(last_taken_ms).max(frame_ms),
+        None => return Err(last_error.unwrap_or_else(|| "No frames were captured".to_string())),
+    }
+    Ok(frames)
+}
+
+fn run_recording(app: &AppHandle, options: &RecordingOptions, stop: &AtomicBool) -> Result<RecordingResult, String> {
+    let frames = record_frames(app, options, stop)?;
+    let frame_count = frames.len() as u32;
+    let duration_ms = frames.iter().map(|f| f.duration_ms as u64).sum();
+
+    let bytes = match options.format {
+        RecordingFormat::Gif => encode_gif(frames, options)?,
+        RecordingFormat::Apng => encode_apng(frames)?,
+    };
+    let path = file_ops::write_file_with_limit(&output_path(options)?, &bytes, MAX_FILE_BYTES)?;
+
+    Ok(RecordingResult {
+        path,
+        format: options.format,
+        frames: frame_count,
+        duration_ms,
+        byte_size: bytes.len(),
+    })
+}
+
+/// Start recording on a background thread
+pub fn start(app: &AppHandle, options: RecordingOptions) -> Result<(), String> {
+    options.validate()?;
+
+    let mut active = ACTIVE_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if active.is_some() {
+        return Err("A recording is already running".to_string());
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    *active = Some(stop.clone());
+
+    tray::set_recording_indicator(app, true);
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let result = run_recording(&app, &options, &stop);
+
+        *ACTIVE_RECORDING
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+        tray::set_recording_indicator(&app, false);
+
+        match result {
+            Ok(result) => {
+                println!("Recording saved: {} ({} frames)", result.path, result.frames);
+                let _ = app.emit("recording-finished", &result);
+            }
+            Err(e) => {
+                println!("Recording failed: {}", e);
+                let _ = app.emit("recording-failed", &e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop the running recording; encoding continues in the background
+pub fn stop() -> bool {
+    let active = ACTIVE_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match active.as_ref() {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Start recording a monitor region to GIF or APNG
+#[tauri::command]
+pub fn start_recording(app: AppHandle, options: RecordingOptions) -> Result<(), String> {
+    start(&app, options)
+}
+
+/// Stop the running recording and save it
+#[tauri::command]
+pub fn stop_recording() -> bool {
+    stop()
+}
+
+/// Whether a recording is in progress
+#[tauri::command]
+pub fn is_recording() -> bool {
+    ACTIVE_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn solid(shade: u8) -> RgbaImage {
+        RgbaImage::from_pixel(4, 4, Rgba([shade, 0, 0, 255]))
+    }
+
+    #[test]
+    fn durations_follow_capture_times() {
+        let mut frames = Vec::new();
+        let mut last_taken_ms = 0;
+        // A slow capture (250 ms, then 350 ms) at a nominal 100 ms interval
+        for (taken_ms, shade) in [(0, 1), (250, 2), (600, 3)] {
+            push_frame(&mut frames, solid(shade), taken_ms, &mut last_taken_ms);
+        }
+        let durations: Vec<u32> = frames.iter().map(|f| f.duration_ms).collect();
+        assert_eq!(durations, vec![250, 350, 0]);
+    }
+
+    #[test]
+    fn identical_frames_merge_into_one_longer_frame() {
+        let mut frames = Vec::new();
+        let mut last_taken_ms = 0;
+        for (taken_ms, shade) in [(0, 1), (100, 1), (200, 1), (300, 2)] {
+            push_frame(&mut frames, solid(shade), taken_ms, &mut last_taken_ms);
+        }
+        let durations: Vec<u32> = frames.iter().map(|f| f.duration_ms).collect();
+        assert_eq!(durations, vec![300, 0]);
+    }
+}
@@ -0,0 +1,564 @@
+// Rich clipboard export
+// Places several representations of one image on the clipboard at once: a bitmap,
+// PNG bytes (image/png), an HTML <img> with a data URI and a reference to the file
+// (text/uri-list), so pasting works in chat apps, file managers and rich-text editors.
+// arboard holds a single format per copy, so each platform is written directly.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::RgbaImage;
+
+use crate::screenshot::image_to_png_bytes;
+
+/// Temp folder holding the PNG behind the file reference when the image was not saved
+const TEMP_DIR_NAME: &str = "beautifullshot-clipboard";
+
+/// Encoded representations of one image
+struct RichContent {
+    png: Vec<u8>,
+    html: String,
+    file: PathBuf,
+}
+
+/// Percent-encoded file:// URL for a local path
+#[cfg(not(target_os = "windows"))]
+fn file_url(path: &Path) -> String {
+    use std::fmt::Write;
+
+    let mut url = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                url.push(byte as char)
+            }
+            _ => {
+                let _ = write!(url, "%{:02X}", byte);
+            }
+        }
+    }
+    url
+}
+
+/// Write the PNG to the temp folder so file managers and chat apps can paste a file
+/// Only the latest clipboard image is kept
+fn write_temp_png(png: &[u8]) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(TEMP_DIR_NAME);
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp folder: {}", e))?;
+
+    let name = chrono::Local::now().format("Screenshot %Y-%m-%d %H-%M-%S.png").to_string();
+    let path = dir.join(name);
+    std::fs::write(&path, png).map_err(|e| format!("Failed to write temp file: {}", e))?;
+    Ok(path)
+}
+
+/// Place an image on the clipboard in every representation the platform supports
+/// `file` is the saved image to reference; a temporary PNG is written when it is `None`
+/// `hold_for` keeps serving the clipboard on Linux (where the owning process must
+/// stay alive) until another app takes ownership or the duration elapses
+pub(crate) fn set(image: &RgbaImage, file: Option<&Path>, hold_for: Option<Duration>) -> Result<(), String> {
+    let png = image_to_png_bytes(image)?;
+    let file = match file {
+        Some(path) if path.is_file() => {
+            std::path::absolute(path).map_err(|e| format!("Invalid path {}: {}", path.display(), e))?
+        }
+        Some(path) => return Err(format!("File not found: {}", path.display())),
+        None => write_temp_png(&png)?,
+    };
+    let html = format!(
+        "<img src=\"data:image/png;base64,{}\" width=\"{}\" height=\"{}\" alt=\"Screenshot\">",
+        STANDARD.encode(&png),
+        image.width(),
+        image.height()
+    );
+    platform::write(image, RichContent { png, html, file }, hold_for)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::time::{Duration, Instant};
+
+    use x11rb::connection::{Connection, RequestConnection};
+    use x11rb::protocol::xproto::{
+        Atom, AtomEnum, ChangeWindowAttributesAux, ConnectionExt as _, CreateWindowAux, EventMask, PropMode,
+        Property, PropertyNotifyEvent, SelectionNotifyEvent, SelectionRequestEvent, Window, WindowClass,
+        SELECTION_NOTIFY_EVENT,
+    };
+    use x11rb::protocol::Event;
+    use x11rb::rust_connection::RustConnection;
+    use x11rb::wrapper::ConnectionExt as _;
+    use x11rb::{COPY_DEPTH_FROM_PARENT, COPY_FROM_PARENT, CURRENT_TIME};
+
+    use image::RgbaImage;
+
+    use super::{file_url, RichContent};
+
+    /// How often a held X11 selection checks its deadline
+    const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+    type Formats = Vec<(&'static str, Vec<u8>)>;
+
+    /// Linux apps take the PNG as the bitmap, so raw pixels are not offered
+    pub(super) fn write(_image: &RgbaImage, content: RichContent, hold_for: Option<Duration>) -> Result<(), String> {
+        let uri = file_url(&content.file);
+        let formats: Formats = vec![
+            ("image/png", content.png),
+            ("text/html", content.html.into_bytes()),
+            ("text/uri-list", format!("{}\r\n", uri).into_bytes()),
+            // GNOME file managers paste files from this target
+            ("x-special/gnome-copied-files", format!("copy\n{}", uri).into_bytes()),
+        ];
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            match write_wayland(formats.clone(), hold_for) {
+                Ok(()) => return Ok(()),
+                Err(e) => println!("Wayland clipboard unavailable, falling back to X11: {}", e),
+            }
+        }
+        X11Owner::acquire(formats)?.serve(hold_for)
+    }
+
+    fn write_wayland(formats: Formats, hold_for: Option<Duration>) -> Result<(), String> {
+        use wl_clipboard_rs::copy::{MimeSource, MimeType, Options, Source};
+
+        let sources = formats
+            .into_iter()
+            .map(|(mime, data)| MimeSource {
+                source: Source::Bytes(data.into_boxed_slice()),
+                mime_type: MimeType::Specific(mime.to_string()),
+            })
+            .collect();
+        // Served from a background thread until another app copies
+        Options::new()
+            .copy_multi(sources)
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        // The thread dies with the process, so short-lived callers wait
+        if let Some(hold_for) = hold_for {
+            std::thread::sleep(hold_for);
+        }
+        Ok(())
+    }
+
+    fn x11_error(e: impl std::fmt::Display) -> String {
+        format!("X11 clipboard error: {}", e)
+    }
+
+    /// A format too large for one request, sent in chunks (ICCCM INCR): the requestor
+    /// deletes the property after reading each chunk to ask for the next one
+    struct Transfer {
+        requestor: Window,
+        property: Atom,
+        target: Atom,
+        /// Index into the owner's formats
+        format: usize,
+        /// Bytes sent so far
+        offset: usize,
+    }
+
+    /// Owner of the X11 CLIPBOARD selection, answering paste requests
+    struct X11Owner {
+        conn: RustConnection,
+        window: Window,
+        targets: Atom,
+        incr: Atom,
+        formats: Vec<(Atom, Vec<u8>)>,
+        /// Largest property written in one request; bigger formats use INCR
+        chunk_size: usize,
+        transfers: Vec<Transfer>,
+    }
+
+    impl X11Owner {
+        fn acquire(formats: Formats) -> Result<Self, String> {
+            let (conn, screen_num) = RustConnection::connect(None).map_err(x11_error)?;
+            let root = conn
+                .setup()
+                .roots
+                .get(screen_num)
+                .ok_or("No X11 screen found")?
+                .root;
+
+            let window = conn.generate_id().map_err(x11_error)?;
+            conn.create_window(
+                COPY_DEPTH_FROM_PARENT,
+                window,
+                root,
+                0,
+                0,
+                1,
+                1,
+                0,
+                WindowClass::INPUT_ONLY,
+                COPY_FROM_PARENT,
+                &CreateWindowAux::new(),
+            )
+            .map_err(x11_error)?;
+
+            let intern = |name: &str| -> Result<Atom, String> {
+                Ok(conn
+                    .intern_atom(false, name.as_bytes())
+                    .map_err(x11_error)?
+                    .reply()
+                    .map_err(x11_error)?
+                    .atom)
+            };
+            let clipboard = intern("CLIPBOARD")?;
+            let targets = intern("TARGETS")?;
+            let incr = intern("INCR")?;
+            let atoms = formats
+                .into_iter()
+                .map(|(mime, data)| Ok((intern(mime)?, data)))
+                .collect::<Result<Vec<_>, String>>()?;
+            // Leave room for the ChangeProperty request header
+            let chunk_size = conn.maximum_request_bytes().saturating_sub(1024);
+
+            conn.set_selection_owner(window, clipboard, CURRENT_TIME)
+                .map_err(x11_error)?;
+            let owner = conn
+                .get_selection_owner(clipboard)
+                .map_err(x11_error)?
+                .reply()
+                .map_err(x11_error)?
+                .owner;
+            if owner != window {
+                return Err("Failed to take clipboard ownership".to_string());
+            }
+
+            Ok(Self {
+                conn,
+                window,
+                targets,
+                incr,
+                formats: atoms,
+                chunk_size,
+                transfers: Vec::new(),
+            })
+        }
+
+        /// Answer requests until another app takes the clipboard
+        /// With `hold_for` this blocks until then (or the deadline); otherwise a
+        /// background thread keeps serving for the life of the process
+        fn serve(mut self, hold_for: Option<Duration>) -> Result<(), String> {
+            match hold_for {
+                Some(hold_for) => self.serve_until(Some(Instant::now() + hold_for)),
+                None => {
+                    std::thread::spawn(move || {
+                        if let Err(e) = self.serve_until(None) {
+                            println!("Clipboard server stopped: {}", e);
+                        }
+                    });
+                    Ok(())
+                }
+            }
+        }
+
+        fn serve_until(&mut self, deadline: Option<Instant>) -> Result<(), String> {
+            let mut cleared = false;
+            loop {
+                let event = match deadline {
+                    None => Some(self.conn.wait_for_event().map_err(x11_error)?),
+                    Some(deadline) if Instant::now() >= deadline => return Ok(()),
+                    Some(_) => self.conn.poll_for_event().map_err(x11_error)?,
+                };
+                match event {
+                    Some(Event::SelectionRequest(request)) => self.answer(request)?,
+                    Some(Event::PropertyNotify(event)) => self.continue_transfer(event)?,
+                    // Another app owns the clipboard now; pastes already underway still finish
+                    Some(Event::SelectionClear(event)) if event.owner == self.window => cleared = true,
+                    Some(_) => {}
+                    None => std::thread::sleep(POLL_INTERVAL),
+                }
+                if cleared && self.transfers.is_empty() {
+                    return Ok(());
+                }
+            }
+        }
+
+        fn answer(&mut self, request: SelectionRequestEvent) -> Result<(), String> {
+            // Obsolete clients leave the property unset and expect the target name
+            let property = if request.property == u32::from(AtomEnum::NONE) {
+                request.target
+            } else {
+                request.property
+            };
+
+            let served = if request.target == self.targets {
+                let mut atoms: Vec<Atom> = self.formats.iter().map(|(atom, _)| *atom).collect();
+                atoms.push(self.targets);
+                self.conn
+                    .change_property32(PropMode::REPLACE, request.requestor, property, AtomEnum::ATOM, &atoms)
+                    .map_err(x11_error)?;
+                true
+            } else if let Some(format) = self.formats.iter().position(|(atom, _)| *atom == request.target) {
+                let data = &self.formats[format].1;
+                if data.len() > self.chunk_size {
+                    // Announce the size; chunks follow as the requestor deletes the property
+                    self.conn
+                        .change_window_attributes(
+                            request.requestor,
+                            &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+                        )
+                        .map_err(x11_error)?;
+                    self.conn
+                        .change_property32(
+                            PropMode::REPLACE,
+                            request.requestor,
+                            property,
+                            self.incr,
+                            &[data.len() as u32],
+                        )
+                        .map_err(x11_error)?;
+                    self.transfers
+                        .retain(|t| !(t.requestor == request.requestor && t.property == property));
+                    self.transfers.push(Transfer {
+                        requestor: request.requestor,
+                        property,
+                        target: request.target,
+                        format,
+                        offset: 0,
+                    });
+                } else {
+                    self.conn
+                        .change_property8(PropMode::REPLACE, request.requestor, property, request.target, data)
+                        .map_err(x11_error)?;
+                }
+                true
+            } else {
+                false
+            };
+
+            self.conn
+                .send_event(
+                    false,
+                    request.requestor,
+                    EventMask::NO_EVENT,
+                    SelectionNotifyEvent {
+                        response_type: SELECTION_NOTIFY_EVENT,
+                        sequence: 0,
+                        time: request.time,
+                        requestor: request.requestor,
+                        selection: request.selection,
+                        target: request.target,
+                        property: if served { property } else { AtomEnum::NONE.into() },
+                    },
+                )
+                .map_err(x11_error)?;
+            self.conn.flush().map_err(x11_error)?;
+            Ok(())
+        }
+
+        /// Send the next INCR chunk once the requestor has read the previous one
+        /// An empty chunk marks the end of the transfer
+        fn continue_transfer(&mut self, event: PropertyNotifyEvent) -> Result<(), String> {
+            if event.state != Property::DELETE {
+                return Ok(());
+            }
+            let Some(index) = self
+                .transfers
+                .iter()
+                .position(|t| t.requestor == event.window && t.property == event.atom)
+            else {
+                return Ok(());
+            };
+
+            let transfer = &mut self.transfers[index];
+            let data = &self.formats[transfer.format].1;
+            let end = (transfer.offset + self.chunk_size).min(data.len());
+            self.conn
+                .change_property8(
+                    PropMode::REPLACE,
+                    transfer.requestor,
+                    transfer.property,
+                    transfer.target,
+                    &data[transfer.offset..end],
+                )
+                .map_err(x11_error)?;
+            let done = transfer.offset == end;
+            transfer.offset = end;
+
+            if done {
+                let finished = self.transfers.remove(index);
+                // Stop watching the requestor unless another transfer to it is running
+                if !self.transfers.iter().any(|t| t.requestor == finished.requestor) {
+                    self.conn
+                        .change_window_attributes(
+                            finished.requestor,
+                            &ChangeWindowAttributesAux::new().event_mask(EventMask::NO_EVENT),
+                        )
+                        .map_err(x11_error)?;
+                }
+            }
+            self.conn.flush().map_err(x11_error)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::time::Duration;
+
+    use image::RgbaImage;
+    use windows::core::w;
+    use windows::Win32::Foundation::{HANDLE, HWND};
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+    use super::RichContent;
+
+    const CF_HDROP: u32 = 15;
+    const CF_DIBV5: u32 = 17;
+
+    /// Bottom-up 32-bit BGRA DIB with a BITMAPV5HEADER, so alpha survives
+    fn dib_v5(image: &RgbaImage) -> Vec<u8> {
+        let (width, height) = image.dimensions();
+        let pixel_bytes = width as usize * height as usize * 4;
+        let mut dib = Vec::with_capacity(124 + pixel_bytes);
+        dib.extend_from_slice(&124u32.to_le_bytes());
+        dib.extend_from_slice(&(width as i32).to_le_bytes());
+        dib.extend_from_slice(&(height as i32).to_le_bytes());
+        dib.extend_from_slice(&1u16.to_le_bytes());
+        dib.extend_from_slice(&32u16.to_le_bytes());
+        // BI_BITFIELDS
+        dib.extend_from_slice(&3u32.to_le_bytes());
+        dib.extend_from_slice(&(pixel_bytes as u32).to_le_bytes());
+        // Resolution, palette size, important colors
+        dib.extend_from_slice(&[0; 16]);
+        for mask in [0x00ff_0000u32, 0x0000_ff00, 0x0000_00ff, 0xff00_0000] {
+            dib.extend_from_slice(&mask.to_le_bytes());
+        }
+        // LCS_sRGB
+        dib.extend_from_slice(b"BGRs");
+        // Endpoints and gamma (unused for sRGB)
+        dib.extend_from_slice(&[0; 48]);
+        // LCS_GM_IMAGES
+        dib.extend_from_slice(&4u32.to_le_bytes());
+        // Profile data, profile size, reserved
+        dib.extend_from_slice(&[0; 12]);
+
+        for row in image.rows().rev() {
+            for pixel in row {
+                let [r, g, b, a] = pixel.0;
+                dib.extend_from_slice(&[b, g, r, a]);
+            }
+        }
+        dib
+    }
+
+    /// Wrap an HTML fragment in the CF_HTML header with byte offsets
+    fn cf_html(fragment: &str) -> Vec<u8> {
+        const PREFIX: &str = "<html><body>\r\n<!--StartFragment-->";
+        const SUFFIX: &str = "<!--EndFragment-->\r\n</body></html>";
+        let header = |start_html: usize, end_html: usize, start_fragment: usize, end_fragment: usize| {
+            format!(
+                "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n",
+                start_html, end_html, start_fragment, end_fragment
+            )
+        };
+
+        let start_html = header(0, 0, 0, 0).len();
+        let start_fragment = start_html + PREFIX.len();
+        let end_fragment = start_fragment + fragment.len();
+        let end_html = end_fragment + SUFFIX.len();
+        format!(
+            "{}{}{}{}",
+            header(start_html, end_html, start_fragment, end_fragment),
+            PREFIX,
+            fragment,
+            SUFFIX
+        )
+        .into_bytes()
+    }
+
+    /// DROPFILES header followed by the double-NUL-terminated UTF-16 file list
+    fn drop_files(path: &std::path::Path) -> Vec<u8> {
+        let mut data = Vec::new();
+        // pFiles (header size), pt.x, pt.y, fNC, fWide
+        for value in [20u32, 0, 0, 0, 1] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for unit in path.to_string_lossy().encode_utf16().chain([0, 0]) {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data
+    }
+
+    /// Copy bytes into movable global memory handed over to the clipboard
+    unsafe fn set_data(format: u32, data: &[u8]) -> Result<(), String> {
+        let memory = GlobalAlloc(GMEM_MOVEABLE, data.len()).map_err(|e| format!("Failed to allocate clipboard memory: {}", e))?;
+        let target = GlobalLock(memory) as *mut u8;
+        if target.is_null() {
+            let _ = GlobalFree(memory);
+            return Err("Failed to lock clipboard memory".to_string());
+        }
+        std::ptr::copy_nonoverlapping(data.as_ptr(), target, data.len());
+        // Reports an error once the lock count reaches zero, which is expected
+        let _ = GlobalUnlock(memory);
+
+        // The clipboard owns the memory only when this succeeds
+        if let Err(e) = SetClipboardData(format, HANDLE(memory.0)) {
+            let _ = GlobalFree(memory);
+            return Err(format!("Failed to copy to clipboard: {}", e));
+        }
+        Ok(())
+    }
+
+    unsafe fn set_all(image: &RgbaImage, content: &RichContent) -> Result<(), String> {
+        EmptyClipboard().map_err(|e| format!("Failed to clear clipboard: {}", e))?;
+        set_data(CF_DIBV5, &dib_v5(image))?;
+        set_data(RegisterClipboardFormatW(w!("PNG")), &content.png)?;
+        set_data(RegisterClipboardFormatW(w!("HTML Format")), &cf_html(&content.html))?;
+        set_data(CF_HDROP, &drop_files(&content.file))
+    }
+
+    /// The clipboard keeps the data after we exit, so `hold_for` is not needed
+    pub(super) fn write(image: &RgbaImage, content: RichContent, _hold_for: Option<Duration>) -> Result<(), String> {
+        unsafe {
+            OpenClipboard(HWND::default()).map_err(|e| format!("Failed to open clipboard: {}", e))?;
+            let result = set_all(image, &content);
+            let _ = CloseClipboard();
+            result
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::time::Duration;
+
+    use objc2_app_kit::{
+        NSPasteboard, NSPasteboardTypeFileURL, NSPasteboardTypeHTML, NSPasteboardTypePNG, NSPasteboardTypeTIFF,
+    };
+    use image::RgbaImage;
+    use objc2_foundation::{NSData, NSString};
+
+    use super::{file_url, RichContent};
+    use crate::export::{self, ExportSpec};
+
+    /// The pasteboard keeps the data after we exit, so `hold_for` is not needed
+    pub(super) fn write(image: &RgbaImage, content: RichContent, _hold_for: Option<Duration>) -> Result<(), String> {
+        // TIFF is the native bitmap type on macOS
+        let tiff = export::encode(image, &ExportSpec::Tiff)?;
+
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        let written = unsafe {
+            pasteboard.setData_forType(Some(&NSData::with_bytes(&tiff)), NSPasteboardTypeTIFF)
+                && pasteboard.setData_forType(Some(&NSData::with_bytes(&content.png)), NSPasteboardTypePNG)
+                && pasteboard.setString_forType(&NSString::from_str(&content.html), NSPasteboardTypeHTML)
+                && pasteboard.setString_forType(&NSString::from_str(&file_url(&content.file)), NSPasteboardTypeFileURL)
+        };
+        if written {
+            Ok(())
+        } else {
+            Err("Failed to write to the pasteboard".to_string())
+        }
+    }
+}
@@ -1,6 +1,6 @@
-// Screenshot capture module using xcap crate
+// Screenshot capture module
 // Provides fullscreen, region, and window capture functionality
-// Falls back to grim on Linux Wayland when xcap fails
+// Pixels come from the capture backend chain (xcap, grim fallback on Wayland)
 // Supports multi-monitor capture with cursor-based monitor detection
 
 use base64::{engine::general_purpose::STANDARD, Engine};
@@ -8,10 +8,13 @@ use image::codecs::png::{CompressionType, FilterType, PngEncoder};
 use image::ImageEncoder;
 use mouse_position::mouse_position::Mouse;
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
 use xcap::{Monitor, Window as XcapWindow};
 
-#[cfg(target_os = "linux")]
-use std::process::Command;
+use crate::capture_backend;
+use crate::capture_store::{CaptureKind, CaptureMetadata, CaptureRef, CaptureStore};
+use crate::window_frame::{self, WindowCaptureOptions};
+use crate::{history, naming};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MonitorInfo {
@@ -22,6 +25,8 @@ pub struct MonitorInfo {
     pub x: i32,
     pub y: i32,
     pub is_primary: bool,
+    /// Display scale (2.0 on Retina/200% displays); width/height are physical pixels
+    pub scale_factor: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,55 +38,14 @@ pub struct WindowInfo {
     pub y: i32,
     pub width: u32,
     pub height: u32,
+    /// Owning process (0 when unknown)
+    pub pid: u32,
+    pub is_focused: bool,
+    pub is_minimized: bool,
 }
 
-/// Check if running on Wayland (Linux only)
-#[cfg(target_os = "linux")]
-fn is_wayland() -> bool {
-    std::env::var("WAYLAND_DISPLAY").is_ok()
-}
-
-/// Capture fullscreen using grim (Wayland-native tool)
-/// Returns base64-encoded PNG on success
-#[cfg(target_os = "linux")]
-fn capture_with_grim() -> Result<String, String> {
-    // grim outputs PNG to stdout with "-" argument
-    let output = Command::new("grim")
-        .arg("-")
-        .output()
-        .map_err(|e| format!("Failed to run grim: {}. Install grim for Wayland support.", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("grim failed: {}", stderr));
-    }
-
-    Ok(STANDARD.encode(&output.stdout))
-}
-
-/// Capture region using grim + slurp (Wayland-native tools)
-/// slurp provides interactive region selection, grim captures it
-#[cfg(target_os = "linux")]
-fn capture_region_with_grim(x: i32, y: i32, width: u32, height: u32) -> Result<String, String> {
-    // grim -g "x,y widthxheight" captures specific region
-    let geometry = format!("{},{} {}x{}", x, y, width, height);
-    let output = Command::new("grim")
-        .arg("-g")
-        .arg(&geometry)
-        .arg("-")
-        .output()
-        .map_err(|e| format!("Failed to run grim: {}. Install grim for Wayland support.", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("grim region capture failed: {}", stderr));
-    }
-
-    Ok(STANDARD.encode(&output.stdout))
-}
-
-/// Convert RgbaImage to base64-encoded PNG string (maximum speed)
-fn image_to_base64_png(img: &image::RgbaImage) -> Result<String, String> {
+/// Encode RgbaImage as PNG bytes (maximum speed)
+pub(crate) fn image_to_png_bytes(img: &image::RgbaImage) -> Result<Vec<u8>, String> {
     // Pre-allocate buffer for speed (estimate: width * height * 4 bytes + overhead)
     let estimated_size = (img.width() * img.height() * 4) as usize + 1024;
     let mut bytes: Vec<u8> = Vec::with_capacity(estimated_size);
@@ -95,81 +59,112 @@ fn image_to_base64_png(img: &image::RgbaImage) -> Result<String, String> {
             image::ExtendedColorType::Rgba8,
         )
         .map_err(|e| e.to_string())?;
-    Ok(STANDARD.encode(&bytes))
+    Ok(bytes)
 }
 
-/// Capture primary monitor - returns base64-encoded PNG
-/// On Linux Wayland: falls back to grim if xcap fails
-#[tauri::command]
-pub fn capture_fullscreen() -> Result<String, String> {
-    // Try xcap first
-    let xcap_result = (|| -> Result<String, String> {
-        let monitors = Monitor::all().map_err(|e| e.to_string())?;
-        let primary = monitors
-            .into_iter()
-            .find(|m| m.is_primary().unwrap_or(false))
-            .ok_or("No primary monitor found")?;
-
-        let image = primary.capture_image().map_err(|e| e.to_string())?;
-
-        // Verify we got a valid image
-        if image.width() == 0 || image.height() == 0 {
-            return Err("Screen recording permission not granted".to_string());
-        }
+/// Convert RgbaImage to base64-encoded PNG string (maximum speed)
+pub(crate) fn image_to_base64_png(img: &image::RgbaImage) -> Result<String, String> {
+    Ok(STANDARD.encode(image_to_png_bytes(img)?))
+}
 
-        image_to_base64_png(&image)
-    })();
+/// Crop a region out of a captured image, clamping it to the image bounds
+pub(crate) fn crop_region(
+    image: &image::RgbaImage,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<image::RgbaImage, String> {
+    let img_width = image.width();
+    let img_height = image.height();
+    let start_x = x.max(0) as u32;
+    let start_y = y.max(0) as u32;
+    let crop_width = width.min(img_width.saturating_sub(start_x));
+    let crop_height = height.min(img_height.saturating_sub(start_y));
 
-    // On Linux Wayland, try grim as fallback if xcap failed
-    #[cfg(target_os = "linux")]
-    if xcap_result.is_err() && is_wayland() {
-        println!("xcap failed on Wayland, trying grim fallback...");
-        return capture_with_grim();
+    if crop_width == 0 || crop_height == 0 {
+        return Err("Invalid region dimensions".to_string());
     }
 
-    xcap_result
+    Ok(image::imageops::crop_imm(image, start_x, start_y, crop_width, crop_height).to_image())
 }
 
-/// Capture specific region from primary monitor - returns base64-encoded PNG
-/// On Linux Wayland: falls back to grim if xcap fails
-#[tauri::command]
-pub fn capture_region(x: i32, y: i32, width: u32, height: u32) -> Result<String, String> {
-    // Try xcap first
-    let xcap_result = (|| -> Result<String, String> {
-        let monitors = Monitor::all().map_err(|e| e.to_string())?;
-        let monitor = monitors
-            .into_iter()
-            .find(|m| m.is_primary().unwrap_or(false))
-            .ok_or("No primary monitor")?;
-
-        let image = monitor.capture_image().map_err(|e| e.to_string())?;
-
-        // Validate region bounds
-        let img_width = image.width();
-        let img_height = image.height();
-        let start_x = x.max(0) as u32;
-        let start_y = y.max(0) as u32;
-        let crop_width = width.min(img_width.saturating_sub(start_x));
-        let crop_height = height.min(img_height.saturating_sub(start_y));
-
-        if crop_width == 0 || crop_height == 0 {
-            return Err("Invalid region dimensions".to_string());
-        }
+/// Describe a monitor-based capture (primary monitor when `None`) for the capture store
+pub(crate) fn monitor_metadata(
+    kind: CaptureKind,
+    monitor_id: Option<u32>,
+    backend: &str,
+) -> CaptureMetadata {
+    let mut metadata = CaptureMetadata::new(kind, backend);
+
+    let monitors = Monitor::all().unwrap_or_default();
+    let monitor = monitors.iter().find(|m| match monitor_id {
+        Some(id) => m.id().unwrap_or(0) == id,
+        None => m.is_primary().unwrap_or(false),
+    });
+    if let Some(monitor) = monitor {
+        metadata.monitor_id = monitor.id().ok();
+        metadata.monitor_name = monitor.name().ok();
+        metadata.scale_factor = monitor.scale_factor().unwrap_or(1.0);
+    }
 
-        // Crop to region
-        let cropped = image::imageops::crop_imm(&image, start_x, start_y, crop_width, crop_height).to_image();
+    if let Some((x, y)) = get_cursor_position() {
+        metadata.cursor_x = Some(x);
+        metadata.cursor_y = Some(y);
+    }
+    metadata
+}
 
-        image_to_base64_png(&cropped)
-    })();
+/// Describe a window capture for the capture store
+pub(crate) fn window_metadata(kind: CaptureKind, window_id: u32, backend: &str) -> CaptureMetadata {
+    let mut metadata = CaptureMetadata::new(kind, backend);
+    metadata.window_id = Some(window_id);
+
+    let windows = XcapWindow::all().unwrap_or_default();
+    if let Some(window) = windows.iter().find(|w| w.id().unwrap_or(0) == window_id) {
+        metadata.app_name = window.app_name().ok();
+        metadata.window_title = window.title().ok();
+        if let Ok(monitor) = window.current_monitor() {
+            metadata.monitor_id = monitor.id().ok();
+            metadata.monitor_name = monitor.name().ok();
+            metadata.scale_factor = monitor.scale_factor().unwrap_or(1.0);
+        }
+    }
 
-    // On Linux Wayland, try grim as fallback if xcap failed
-    #[cfg(target_os = "linux")]
-    if xcap_result.is_err() && is_wayland() {
-        println!("xcap region capture failed on Wayland, trying grim fallback...");
-        return capture_region_with_grim(x, y, width, height);
+    if let Some((x, y)) = get_cursor_position() {
+        metadata.cursor_x = Some(x);
+        metadata.cursor_y = Some(y);
     }
+    metadata
+}
+
+/// Capture primary monitor - returns base64-encoded PNG
+#[tauri::command]
+pub fn capture_fullscreen() -> Result<String, String> {
+    let capture = capture_backend::registry().capture_monitor(None)?;
+    image_to_base64_png(&capture.image)
+}
 
-    xcap_result
+/// Capture primary monitor into the capture store
+#[tauri::command]
+pub fn capture_fullscreen_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+) -> Result<CaptureRef, String> {
+    let capture = capture_backend::registry().capture_monitor(None)?;
+    let metadata = monitor_metadata(CaptureKind::Monitor, None, capture.backend);
+    let capture_ref = store.insert(capture.image, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
+}
+
+/// Capture specific region from primary monitor - returns base64-encoded PNG
+#[tauri::command]
+pub fn capture_region(x: i32, y: i32, width: u32, height: u32) -> Result<String, String> {
+    let capture = capture_backend::registry().capture_monitor(None)?;
+    let cropped = crop_region(&capture.image, x, y, width, height)?;
+    image_to_base64_png(&cropped)
 }
 
 /// Minimum window size to filter out icons and tiny windows
@@ -198,23 +193,16 @@ pub fn get_windows() -> Result<Vec<WindowInfo>, String> {
             y: w.y().unwrap_or(0),
             width,
             height,
+            pid: w.pid().unwrap_or(0),
+            is_focused: w.is_focused().unwrap_or(false),
+            is_minimized: w.is_minimized().unwrap_or(false),
         });
     }
     Ok(result)
 }
 
-/// Capture window thumbnail (small preview) - returns base64-encoded PNG
-#[tauri::command]
-pub fn get_window_thumbnail(window_id: u32, max_size: u32) -> Result<String, String> {
-    let windows = XcapWindow::all().map_err(|e| e.to_string())?;
-    let window = windows
-        .into_iter()
-        .find(|w| w.id().unwrap_or(0) == window_id)
-        .ok_or("Window not found")?;
-
-    let image = window.capture_image().map_err(|e| e.to_string())?;
-
-    // Resize to thumbnail
+/// Resize an image so its longest side is `max_size`, keeping aspect ratio
+pub(crate) fn resize_to_fit(image: &image::RgbaImage, max_size: u32) -> image::RgbaImage {
     let (width, height) = (image.width(), image.height());
     let (new_width, new_height) = if width > height {
         let ratio = max_size as f32 / width as f32;
@@ -224,21 +212,50 @@ pub fn get_window_thumbnail(window_id: u32, max_size: u32) -> Result<String, Str
         ((width as f32 * ratio) as u32, max_size)
     };
 
-    let thumbnail = image::imageops::resize(&image, new_width, new_height, image::imageops::FilterType::Lanczos3);
-    image_to_base64_png(&thumbnail)
+    image::imageops::resize(image, new_width, new_height, image::imageops::FilterType::Lanczos3)
+}
+
+/// Capture window thumbnail (small preview) - returns base64-encoded PNG
+#[tauri::command]
+pub fn get_window_thumbnail(window_id: u32, max_size: u32) -> Result<String, String> {
+    let image = capture_backend::registry().capture_window(window_id)?.image;
+    image_to_base64_png(&resize_to_fit(&image, max_size))
+}
+
+/// Capture window thumbnail into the capture store
+#[tauri::command]
+pub fn get_window_thumbnail_ref(
+    store: State<'_, CaptureStore>,
+    window_id: u32,
+    max_size: u32,
+) -> Result<CaptureRef, String> {
+    let capture = capture_backend::registry().capture_window(window_id)?;
+    let metadata = window_metadata(CaptureKind::Thumbnail, window_id, capture.backend);
+    Ok(store.insert(resize_to_fit(&capture.image, max_size), metadata))
 }
 
 /// Capture specific window by ID - returns base64-encoded PNG
+/// `options` selects content only, frame or shadow (platform-native when omitted)
 #[tauri::command]
-pub fn capture_window(window_id: u32) -> Result<String, String> {
-    let windows = XcapWindow::all().map_err(|e| e.to_string())?;
-    let window = windows
-        .into_iter()
-        .find(|w| w.id().unwrap_or(0) == window_id)
-        .ok_or("Window not found")?;
+pub fn capture_window(window_id: u32, options: Option<WindowCaptureOptions>) -> Result<String, String> {
+    let capture = window_frame::capture(window_id, &options.unwrap_or_default())?;
+    image_to_base64_png(&capture.image)
+}
 
-    let image = window.capture_image().map_err(|e| e.to_string())?;
-    image_to_base64_png(&image)
+/// Capture specific window by ID into the capture store
+#[tauri::command]
+pub fn capture_window_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    window_id: u32,
+    options: Option<WindowCaptureOptions>,
+) -> Result<CaptureRef, String> {
+    let capture = window_frame::capture(window_id, &options.unwrap_or_default())?;
+    let metadata = window_metadata(CaptureKind::Window, window_id, capture.backend);
+    let capture_ref = store.insert(capture.image, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
 }
 
 /// Get monitor list
@@ -256,13 +273,14 @@ pub fn get_monitors() -> Result<Vec<MonitorInfo>, String> {
             x: m.x().unwrap_or(0),
             y: m.y().unwrap_or(0),
             is_primary: m.is_primary().unwrap_or(false),
+            scale_factor: m.scale_factor().unwrap_or(1.0),
         });
     }
     Ok(result)
 }
 
 /// Get current cursor position
-fn get_cursor_position() -> Option<(i32, i32)> {
+pub(crate) fn get_cursor_position() -> Option<(i32, i32)> {
     match Mouse::get_mouse_position() {
         Mouse::Position { x, y } => Some((x, y)),
         Mouse::Error => None,
@@ -299,25 +317,30 @@ pub fn get_cursor_monitor() -> Result<MonitorInfo, String> {
         x: monitor.x().unwrap_or(0),
         y: monitor.y().unwrap_or(0),
         is_primary: monitor.is_primary().unwrap_or(false),
+        scale_factor: monitor.scale_factor().unwrap_or(1.0),
     })
 }
 
 /// Capture specific monitor by ID - returns base64-encoded PNG
 #[tauri::command]
 pub fn capture_monitor(monitor_id: u32) -> Result<String, String> {
-    let monitors = Monitor::all().map_err(|e| e.to_string())?;
-    let monitor = monitors
-        .into_iter()
-        .find(|m| m.id().unwrap_or(0) == monitor_id)
-        .ok_or("Monitor not found")?;
-
-    let image = monitor.capture_image().map_err(|e| e.to_string())?;
-
-    if image.width() == 0 || image.height() == 0 {
-        return Err("Screen recording permission not granted".to_string());
-    }
+    let capture = capture_backend::registry().capture_monitor(Some(monitor_id))?;
+    image_to_base64_png(&capture.image)
+}
 
-    image_to_base64_png(&image)
+/// Capture specific monitor by ID into the capture store
+#[tauri::command]
+pub fn capture_monitor_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    monitor_id: u32,
+) -> Result<CaptureRef, String> {
+    let capture = capture_backend::registry().capture_monitor(Some(monitor_id))?;
+    let metadata = monitor_metadata(CaptureKind::Monitor, Some(monitor_id), capture.backend);
+    let capture_ref = store.insert(capture.image, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
 }
 
 /// Capture specific region from a specific monitor - returns base64-encoded PNG
@@ -330,28 +353,28 @@ pub fn capture_region_from_monitor(
     height: u32,
     monitor_id: u32,
 ) -> Result<String, String> {
-    let monitors = Monitor::all().map_err(|e| e.to_string())?;
-    let monitor = monitors
-        .into_iter()
-        .find(|m| m.id().unwrap_or(0) == monitor_id)
-        .ok_or("Monitor not found")?;
-
-    let image = monitor.capture_image().map_err(|e| e.to_string())?;
-
-    // Validate region bounds (coordinates are relative to monitor)
-    let img_width = image.width();
-    let img_height = image.height();
-    let start_x = x.max(0) as u32;
-    let start_y = y.max(0) as u32;
-    let crop_width = width.min(img_width.saturating_sub(start_x));
-    let crop_height = height.min(img_height.saturating_sub(start_y));
-
-    if crop_width == 0 || crop_height == 0 {
-        return Err("Invalid region dimensions".to_string());
-    }
-
-    let cropped =
-        image::imageops::crop_imm(&image, start_x, start_y, crop_width, crop_height).to_image();
-
+    let capture = capture_backend::registry().capture_monitor(Some(monitor_id))?;
+    let cropped = crop_region(&capture.image, x, y, width, height)?;
     image_to_base64_png(&cropped)
 }
+
+/// Capture specific region from a specific monitor into the capture store
+/// Coordinates are relative to the monitor's origin
+#[tauri::command]
+pub fn capture_region_from_monitor_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    monitor_id: u32,
+) -> Result<CaptureRef, String> {
+    let capture = capture_backend::registry().capture_monitor(Some(monitor_id))?;
+    let cropped = crop_region(&capture.image, x, y, width, height)?;
+    let metadata = monitor_metadata(CaptureKind::Region, Some(monitor_id), capture.backend);
+    let capture_ref = store.insert(cropped, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
+}
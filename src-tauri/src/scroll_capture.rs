@@ -0,0 +1,171 @@
+// Scrolling (long page) capture session
+// Repeatedly grabs a monitor region while the user scrolls and feeds the frames
+// to the stitcher; the tall result lands in the capture store when stopped
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::capture_store::{CaptureKind, CaptureRef, CaptureStore};
+use crate::screenshot::{crop_region, monitor_metadata};
+use crate::stitch::{stitch_frames, StitchOutcome, Stitcher};
+use crate::{capture_backend, history, naming};
+
+/// Shortest allowed delay between frames
+const MIN_INTERVAL_MS: u64 = 50;
+
+/// Frames after which the session stops on its own
+const MAX_FRAMES: u32 = 2_000;
+
+/// Stop flag of the running session, if any
+static ACTIVE_SESSION: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+fn default_interval_ms() -> u64 {
+    250
+}
+
+/// Region to scroll-capture, relative to the monitor's origin
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScrollCaptureOptions {
+    pub monitor_id: u32,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+/// Progress payload emitted per captured frame
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrollCaptureProgress {
+    pub frames: u32,
+    pub height: u32,
+    /// "appended", "unchanged" or "no_overlap"
+    pub outcome: &'static str,
+}
+
+/// Capture one frame, returning it with the backend that produced it
+fn capture_frame(options: &ScrollCaptureOptions) -> Result<(image::RgbaImage, &'static str), String> {
+    let capture = capture_backend::registry().capture_monitor(Some(options.monitor_id))?;
+    let frame = crop_region(&capture.image, options.x, options.y, options.width, options.height)?;
+    Ok((frame, capture.backend))
+}
+
+fn run_session(app: &AppHandle, options: &ScrollCaptureOptions, stop: &AtomicBool) -> Result<CaptureRef, String> {
+    let interval = Duration::from_millis(options.interval_ms);
+    let (first, backend) = capture_frame(options)?;
+    let mut stitcher = Stitcher::new(first);
+    let mut frames = 1;
+
+    while !stop.load(Ordering::SeqCst) && frames < MAX_FRAMES {
+        thread::sleep(interval);
+        // A failed capture or the height limit ends the session: keep what we have
+        let outcome = match capture_frame(options).and_then(|(frame, _)| stitcher.push(frame)) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                println!("Scroll capture stopped: {}", e);
+                break;
+            }
+        };
+        frames += 1;
+
+        let _ = app.emit(
+            "scroll-capture-progress",
+            ScrollCaptureProgress {
+                frames,
+                height: stitcher.height(),
+                outcome: match outcome {
+                    StitchOutcome::Appended(_) => "appended",
+                    StitchOutcome::Unchanged => "unchanged",
+                    StitchOutcome::NoOverlap => "no_overlap",
+                },
+            },
+        );
+    }
+
+    let image = stitcher.finish();
+    let metadata = monitor_metadata(CaptureKind::Region, Some(options.monitor_id), backend);
+    let capture_ref = app.state::<CaptureStore>().insert(image, metadata);
+    history::auto_record(app, &capture_ref);
+    naming::auto_save(app, &capture_ref);
+    Ok(capture_ref)
+}
+
+/// Start a scrolling capture session on a background thread
+#[tauri::command]
+pub fn start_scroll_capture(app: AppHandle, options: ScrollCaptureOptions) -> Result<(), String> {
+    if options.width == 0 || options.height == 0 {
+        return Err("Scroll capture region is empty".to_string());
+    }
+    if options.interval_ms < MIN_INTERVAL_MS {
+        return Err(format!("Interval must be at least {} ms", MIN_INTERVAL_MS));
+    }
+
+    let mut active = ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if active.is_some() {
+        return Err("A scrolling capture is already running".to_string());
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    *active = Some(stop.clone());
+
+    thread::spawn(move || {
+        let result = run_session(&app, &options, &stop);
+        *ACTIVE_SESSION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+
+        match result {
+            Ok(capture_ref) => {
+                let _ = app.emit("scroll-capture-finished", &capture_ref);
+            }
+            Err(e) => {
+                println!("Scroll capture failed: {}", e);
+                let _ = app.emit("scroll-capture-failed", &e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop the scrolling capture; the stitched image arrives via scroll-capture-finished
+#[tauri::command]
+pub fn stop_scroll_capture() -> bool {
+    let active = ACTIVE_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match active.as_ref() {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Stitch already-captured frames (top to bottom scroll order) into a new capture
+#[tauri::command]
+pub async fn stitch_captures(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    capture_ids: Vec<String>,
+) -> Result<CaptureRef, String> {
+    let first_id = capture_ids.first().ok_or("No frames to stitch")?;
+    let metadata = store.metadata(first_id).ok_or("Capture not found")?;
+    let frames = capture_ids
+        .iter()
+        .map(|id| store.get(id).map(|image| (*image).clone()).ok_or("Capture not found"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let capture_ref = store.insert(stitch_frames(frames)?, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
+}
@@ -1,8 +1,23 @@
 // Global shortcuts - register system-wide keyboard shortcuts
 
-use tauri::{Emitter, Manager};
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
+use crate::capture_store::{CaptureRef, CaptureStore};
+use crate::tray;
+use crate::window_frame::WindowCaptureOptions;
+use crate::window_match::{self, WindowQuery};
+
+/// Shortcut that captures the best window match for a query
+#[derive(Debug, Clone, Deserialize)]
+pub struct WindowMatchShortcut {
+    pub hotkey: String,
+    pub query: WindowQuery,
+    #[serde(default)]
+    pub options: Option<WindowCaptureOptions>,
+}
+
 /// Parse hotkey string to Shortcut
 /// Format: "Modifier+Modifier+Key" (e.g., "CommandOrControl+Shift+C")
 fn parse_hotkey(hotkey: &str) -> Option<Shortcut> {
@@ -130,6 +145,39 @@ fn register_shortcut(
     Ok(())
 }
 
+/// Register a global shortcut that captures in the backend, without a frontend round trip
+/// Emits "hotkey-capture-complete" (CaptureRef) or "hotkey-capture-failed" (error string)
+fn register_capture<F>(app: &AppHandle, hotkey: &str, name: &str, capture: F) -> Result<(), Box<dyn std::error::Error>>
+where
+    F: Fn(&AppHandle) -> Result<CaptureRef, String> + Clone + Send + Sync + 'static,
+{
+    let shortcut =
+        parse_hotkey(hotkey).ok_or_else(|| format!("Invalid hotkey format: {}", hotkey))?;
+
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state != ShortcutState::Pressed {
+                return;
+            }
+            // Capturing blocks; keep the shortcut handler thread free
+            let app = app.clone();
+            let capture = capture.clone();
+            std::thread::spawn(move || match capture(&app) {
+                Ok(capture_ref) => {
+                    tray::show_main_window(&app);
+                    let _ = app.emit_to("main", "hotkey-capture-complete", &capture_ref);
+                }
+                Err(e) => {
+                    println!("Shortcut capture failed: {}", e);
+                    let _ = app.emit_to("main", "hotkey-capture-failed", &e);
+                }
+            });
+        })?;
+
+    println!("Registered shortcut: {} -> {}", hotkey, name);
+    Ok(())
+}
+
 /// Tauri command to update shortcuts from frontend settings
 /// Returns list of shortcuts that failed to register (empty if all succeeded)
 #[tauri::command]
@@ -138,6 +186,8 @@ pub fn update_shortcuts(
     capture: String,
     capture_region: String,
     capture_window: String,
+    capture_active_window: Option<String>,
+    window_match: Option<WindowMatchShortcut>,
 ) -> Result<Vec<String>, String> {
     // Unregister all existing shortcuts
     let _ = app.global_shortcut().unregister_all();
@@ -160,6 +210,22 @@ pub fn update_shortcuts(
             errors.push(format!("Capture Window ({}): {}", capture_window, e));
         }
     }
+    if let Some(hotkey) = capture_active_window.filter(|h| !h.is_empty()) {
+        let capture = |app: &AppHandle| {
+            window_match::capture_active_window(app.clone(), app.state::<CaptureStore>(), None)
+        };
+        if let Err(e) = register_capture(&app, &hotkey, "capture-active-window", capture) {
+            errors.push(format!("Capture Active Window ({}): {}", hotkey, e));
+        }
+    }
+    if let Some(WindowMatchShortcut { hotkey, query, options }) = window_match.filter(|m| !m.hotkey.is_empty()) {
+        let capture = move |app: &AppHandle| {
+            window_match::capture_window_matching(app.clone(), app.state::<CaptureStore>(), query.clone(), options)
+        };
+        if let Err(e) = register_capture(&app, &hotkey, "capture-window-matching", capture) {
+            errors.push(format!("Capture Matching Window ({}): {}", hotkey, e));
+        }
+    }
 
     Ok(errors)
 }
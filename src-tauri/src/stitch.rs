@@ -0,0 +1,416 @@
+// Scrolling capture stitching
+// Pure image logic, no capture or Tauri dependencies: successive frames of a
+// scrolled region are aligned by matching per-row hashes and joined into one
+// tall image. Rows that stay put between frames (sticky headers/footers) are
+// detected once and kept only at the top/bottom of the result.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use image::RgbaImage;
+
+/// Minimum rows two frames must share to trust an alignment
+const MIN_OVERLAP_ROWS: usize = 16;
+
+/// Minimum distinct rows in the overlap (blank areas match at any offset)
+const MIN_DISTINCT_ROWS: usize = 4;
+
+/// Sticky header/footer may cover at most this fraction of the frame each
+const MAX_STICKY_FRACTION: usize = 3;
+
+/// Upper bound on the stitched image height
+pub const MAX_STITCHED_HEIGHT: u32 = 40_000;
+
+/// What happened when a frame was added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StitchOutcome {
+    /// Content scrolled; this many new rows were appended
+    Appended(u32),
+    /// Nothing scrolled: the frame matches the previous one, or redraws it in place
+    /// and replaces it
+    Unchanged,
+    /// No reliable overlap with the previous frame; its content was appended as a
+    /// new segment and later frames align to it
+    NoOverlap,
+}
+
+/// Hash every pixel row of an image
+pub fn row_hashes(image: &RgbaImage) -> Vec<u64> {
+    let row_len = image.width() as usize * 4;
+    image
+        .as_raw()
+        .chunks_exact(row_len)
+        .map(|row| {
+            let mut hasher = DefaultHasher::new();
+            row.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Rows at the top and bottom that are identical in both frames
+fn sticky_rows(prev: &[u64], next: &[u64]) -> (usize, usize) {
+    let max = prev.len() / MAX_STICKY_FRACTION;
+    let header = prev
+        .iter()
+        .zip(next)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(max);
+    let footer = prev
+        .iter()
+        .rev()
+        .zip(next.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(max);
+    (header, footer)
+}
+
+/// Find how far content scrolled between two frames' content rows
+/// Returns the smallest shift where `prev[shift..]` lines up with the start of `next`
+pub fn find_scroll_offset(prev: &[u64], next: &[u64]) -> Option<usize> {
+    let len = prev.len().min(next.len());
+    if len <= MIN_OVERLAP_ROWS {
+        return None;
+    }
+
+    (1..=len - MIN_OVERLAP_ROWS).find(|&shift| {
+        let overlap = &prev[shift..len];
+        if overlap != &next[..len - shift] {
+            return false;
+        }
+        let distinct: HashSet<&u64> = overlap.iter().collect();
+        distinct.len() >= MIN_DISTINCT_ROWS
+    })
+}
+
+/// Whether `next` is `prev` redrawn without scrolling: at least half the rows stay
+/// where they were, and enough of them are distinct that blank areas don't count
+fn changed_in_place(prev: &[u64], next: &[u64]) -> bool {
+    let same: Vec<&u64> = prev.iter().zip(next).filter(|(a, b)| a == b).map(|(a, _)| a).collect();
+    let distinct: HashSet<&u64> = same.iter().copied().collect();
+    same.len() * 2 >= prev.len() && distinct.len() >= MIN_DISTINCT_ROWS
+}
+
+/// Copy rows `[start, end)` of an image as raw RGBA bytes
+fn rows(image: &RgbaImage, start: usize, end: usize) -> &[u8] {
+    let row_len = image.width() as usize * 4;
+    &image.as_raw()[start * row_len..end * row_len]
+}
+
+/// Incrementally stitches frames of a scrolling region (scrolling down)
+pub struct Stitcher {
+    width: u32,
+    height: u32,
+    /// Sticky (header, footer) rows, fixed at the first scroll
+    sticky: Option<(usize, usize)>,
+    prev_frame: RgbaImage,
+    prev_hashes: Vec<u64>,
+    /// Rows stitched so far, excluding the footer once the sticky rows are known
+    body: Vec<u8>,
+}
+
+impl Stitcher {
+    /// Start from the first frame
+    pub fn new(first: RgbaImage) -> Self {
+        let prev_hashes = row_hashes(&first);
+        Self {
+            width: first.width(),
+            height: first.height(),
+            sticky: None,
+            body: first.as_raw().clone(),
+            prev_frame: first,
+            prev_hashes,
+        }
+    }
+
+    fn footer_rows(&self) -> usize {
+        self.sticky.map(|(_, footer)| footer).unwrap_or(0)
+    }
+
+    /// Current stitched height
+    pub fn height(&self) -> u32 {
+        let row_len = self.width as usize * 4;
+        let body_rows = self.body.len() / row_len;
+        // Before the first verified scroll the body holds whole frames
+        match self.sticky {
+            Some((_, footer)) => (body_rows + footer) as u32,
+            None => body_rows as u32,
+        }
+    }
+
+    /// Add the next frame
+    pub fn push(&mut self, frame: RgbaImage) -> Result<StitchOutcome, String> {
+        if frame.dimensions() != (self.width, self.height) {
+            return Err("Frame size changed during scrolling capture".to_string());
+        }
+
+        let hashes = row_hashes(&frame);
+        if hashes == self.prev_hashes {
+            return Ok(StitchOutcome::Unchanged);
+        }
+
+        // Until a scroll is verified the sticky rows are only an estimate from this pair
+        let (header, footer) = self
+            .sticky
+            .unwrap_or_else(|| sticky_rows(&self.prev_hashes, &hashes));
+        let content_end = self.height as usize - footer;
+        if content_end <= header {
+            return Ok(StitchOutcome::NoOverlap);
+        }
+
+        let prev_content = &self.prev_hashes[header..content_end];
+        let next_content = &hashes[header..content_end];
+        match find_scroll_offset(prev_content, next_content) {
+            Some(shift) => {
+                self.check_height(shift)?;
+                if self.sticky.is_none() {
+                    // Drop the footer of the last frame; it is re-added from the final frame
+                    let row_len = self.width as usize * 4;
+                    self.body.truncate(self.body.len() - footer * row_len);
+                    self.sticky = Some((header, footer));
+                }
+                self.body
+                    .extend_from_slice(rows(&frame, content_end - shift, content_end));
+                self.set_prev(frame, hashes);
+                Ok(StitchOutcome::Appended(shift as u32))
+            }
+            // Caret blink, spinner or hover highlight: redraw the last frame instead of
+            // appending its content again
+            None if changed_in_place(prev_content, next_content) => {
+                let (start, end) = self.segment_rows();
+                let row_len = self.width as usize * 4;
+                self.body.truncate(self.body.len() - (end - start) * row_len);
+                self.body.extend_from_slice(rows(&frame, start, end));
+                self.set_prev(frame, hashes);
+                Ok(StitchOutcome::Unchanged)
+            }
+            // No overlap (e.g. scrolled too far between frames): the frame starts a new
+            // segment, so the capture keeps going from it
+            None => {
+                let (start, end) = self.segment_rows();
+                self.check_height(end - start)?;
+                self.body.extend_from_slice(rows(&frame, start, end));
+                self.set_prev(frame, hashes);
+                Ok(StitchOutcome::NoOverlap)
+            }
+        }
+    }
+
+    /// Rows of each frame that go into the body: the content area once the sticky
+    /// rows are known, the whole frame before that
+    fn segment_rows(&self) -> (usize, usize) {
+        let (header, footer) = self.sticky.unwrap_or((0, 0));
+        (header, self.height as usize - footer)
+    }
+
+    fn check_height(&self, new_rows: usize) -> Result<(), String> {
+        if self.height() as usize + new_rows > MAX_STITCHED_HEIGHT as usize {
+            return Err(format!("Stitched image would exceed {} rows", MAX_STITCHED_HEIGHT));
+        }
+        Ok(())
+    }
+
+    fn set_prev(&mut self, frame: RgbaImage, hashes: Vec<u64>) {
+        self.prev_frame = frame;
+        self.prev_hashes = hashes;
+    }
+
+    /// Produce the stitched image
+    pub fn finish(self) -> RgbaImage {
+        let footer = self.footer_rows();
+        let mut data = self.body;
+        if self.sticky.is_some() {
+            data.extend_from_slice(rows(
+                &self.prev_frame,
+                self.height as usize - footer,
+                self.height as usize,
+            ));
+        }
+        let height = (data.len() / (self.width as usize * 4)) as u32;
+        RgbaImage::from_raw(self.width, height, data).unwrap_or_default()
+    }
+}
+
+/// Stitch a complete sequence of frames; frames that don't align start a new segment
+pub fn stitch_frames(frames: Vec<RgbaImage>) -> Result<RgbaImage, String> {
+    let mut frames = frames.into_iter();
+    let first = frames.next().ok_or("No frames to stitch")?;
+    let mut stitcher = Stitcher::new(first);
+    for frame in frames {
+        stitcher.push(frame)?;
+    }
+    Ok(stitcher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const WIDTH: u32 = 8;
+
+    /// Tall page whose rows are all distinct (vertical and horizontal gradient)
+    fn page(height: u32) -> RgbaImage {
+        RgbaImage::from_fn(WIDTH, height, |x, y| {
+            Rgba([(y % 256) as u8, (y / 256) as u8, (x * 16) as u8, 255])
+        })
+    }
+
+    /// Solid band standing in for a sticky header or footer
+    fn band(height: u32, shade: u8) -> RgbaImage {
+        RgbaImage::from_fn(WIDTH, height, |_, y| Rgba([shade, y as u8, 50, 255]))
+    }
+
+    /// Stack images vertically
+    fn stack(parts: &[&RgbaImage]) -> RgbaImage {
+        let data: Vec<u8> = parts.iter().flat_map(|p| p.as_raw().iter().copied()).collect();
+        let height = (data.len() / (WIDTH as usize * 4)) as u32;
+        RgbaImage::from_raw(WIDTH, height, data).unwrap()
+    }
+
+    /// Rows `[top, top + height)` of an image
+    fn slice(image: &RgbaImage, top: u32, height: u32) -> RgbaImage {
+        image::imageops::crop_imm(image, 0, top, WIDTH, height).to_image()
+    }
+
+    #[test]
+    fn finds_scroll_offset_between_frames() {
+        let hashes = row_hashes(&page(200));
+        assert_eq!(find_scroll_offset(&hashes[0..100], &hashes[30..130]), Some(30));
+        assert_eq!(find_scroll_offset(&hashes[0..100], &hashes[1..101]), Some(1));
+    }
+
+    #[test]
+    fn no_scroll_offset_without_enough_overlap() {
+        let hashes = row_hashes(&page(300));
+        // Overlap shorter than MIN_OVERLAP_ROWS
+        assert_eq!(find_scroll_offset(&hashes[0..100], &hashes[90..190]), None);
+        // Disjoint content
+        assert_eq!(find_scroll_offset(&hashes[0..100], &hashes[150..250]), None);
+    }
+
+    #[test]
+    fn blank_rows_do_not_align() {
+        let blank = vec![7u64; 100];
+        assert_eq!(find_scroll_offset(&blank, &blank), None);
+    }
+
+    #[test]
+    fn detects_sticky_header_and_footer() {
+        let content = page(200);
+        let header = band(10, 200);
+        let footer = band(6, 100);
+        let prev = stack(&[&header, &slice(&content, 0, 80), &footer]);
+        let next = stack(&[&header, &slice(&content, 40, 80), &footer]);
+        assert_eq!(sticky_rows(&row_hashes(&prev), &row_hashes(&next)), (10, 6));
+    }
+
+    #[test]
+    fn sticky_rows_are_capped() {
+        let frame = page(90);
+        let hashes = row_hashes(&frame);
+        assert_eq!(sticky_rows(&hashes, &hashes), (30, 30));
+    }
+
+    #[test]
+    fn identical_frame_is_unchanged() {
+        let frame = slice(&page(200), 0, 100);
+        let mut stitcher = Stitcher::new(frame.clone());
+        assert_eq!(stitcher.push(frame).unwrap(), StitchOutcome::Unchanged);
+        assert_eq!(stitcher.height(), 100);
+    }
+
+    /// Frame with one content row recolored, like a blinking caret
+    fn blink(frame: &RgbaImage, row: u32) -> RgbaImage {
+        let mut frame = frame.clone();
+        for x in 0..WIDTH {
+            frame.put_pixel(x, row, Rgba([255, 0, 255, 255]));
+        }
+        frame
+    }
+
+    #[test]
+    fn change_without_scroll_replaces_last_frame() {
+        let content = page(300);
+        let frame = slice(&content, 0, 120);
+        let mut stitcher = Stitcher::new(frame.clone());
+        assert_eq!(stitcher.push(blink(&frame, 60)).unwrap(), StitchOutcome::Unchanged);
+        assert_eq!(stitcher.push(frame.clone()).unwrap(), StitchOutcome::Unchanged);
+        assert_eq!(stitcher.height(), 120);
+
+        assert_eq!(stitcher.push(slice(&content, 40, 120)).unwrap(), StitchOutcome::Appended(40));
+        assert_eq!(stitcher.finish(), slice(&content, 0, 160));
+    }
+
+    #[test]
+    fn change_without_scroll_keeps_the_redrawn_rows() {
+        let content = page(300);
+        let frame = slice(&content, 0, 120);
+        let mut stitcher = Stitcher::new(frame.clone());
+        stitcher.push(blink(&frame, 100)).unwrap();
+        assert_eq!(stitcher.finish(), blink(&frame, 100));
+    }
+
+    #[test]
+    fn change_without_scroll_does_not_fix_sticky_rows() {
+        let content = page(400);
+        let header = band(10, 200);
+        let footer = band(6, 100);
+        let frame = |top| stack(&[&header, &slice(&content, top, 80), &footer]);
+
+        let mut stitcher = Stitcher::new(frame(0));
+        assert_eq!(stitcher.push(blink(&frame(0), 50)).unwrap(), StitchOutcome::Unchanged);
+        assert_eq!(stitcher.push(frame(50)).unwrap(), StitchOutcome::Appended(50));
+
+        // The redrawn row is kept, at the same place in the stitched image
+        let expected = blink(&stack(&[&header, &slice(&content, 0, 130), &footer]), 50);
+        assert_eq!(stitcher.finish(), expected);
+    }
+
+    #[test]
+    fn frame_without_overlap_starts_new_segment() {
+        let content = page(600);
+        let mut stitcher = Stitcher::new(slice(&content, 0, 100));
+        assert_eq!(stitcher.push(slice(&content, 300, 100)).unwrap(), StitchOutcome::NoOverlap);
+        assert_eq!(stitcher.height(), 200);
+        // Later frames align with the new segment instead of stalling
+        assert_eq!(stitcher.push(slice(&content, 340, 100)).unwrap(), StitchOutcome::Appended(40));
+
+        let expected = stack(&[&slice(&content, 0, 100), &slice(&content, 300, 140)]);
+        assert_eq!(stitcher.finish(), expected);
+    }
+
+    #[test]
+    fn rejects_frame_size_change() {
+        let content = page(200);
+        let mut stitcher = Stitcher::new(slice(&content, 0, 100));
+        assert!(stitcher.push(slice(&content, 10, 90)).is_err());
+    }
+
+    #[test]
+    fn stitches_gradient_frames() {
+        let content = page(400);
+        let frames = [0, 35, 35, 90, 160, 220]
+            .iter()
+            .map(|&top| slice(&content, top, 120))
+            .collect();
+        assert_eq!(stitch_frames(frames).unwrap(), slice(&content, 0, 340));
+    }
+
+    #[test]
+    fn stitches_gradient_frames_with_sticky_bars() {
+        let content = page(400);
+        let header = band(10, 200);
+        let footer = band(6, 100);
+        let frames = [0, 50, 100, 150]
+            .iter()
+            .map(|&top| stack(&[&header, &slice(&content, top, 80), &footer]))
+            .collect();
+
+        let expected = stack(&[&header, &slice(&content, 0, 230), &footer]);
+        assert_eq!(stitch_frames(frames).unwrap(), expected);
+    }
+}
@@ -0,0 +1,160 @@
+// Delayed (timed) capture driven by the backend
+// Hides the main window, counts down with tick events and the tray tooltip,
+// then captures so menus and tooltips opened meanwhile end up in the shot
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::capture_store::{CaptureRef, CaptureStore};
+use crate::{overlay, screenshot, tray};
+
+/// Longest delay accepted for a timed capture
+const MAX_DELAY_SECONDS: u32 = 60;
+
+/// How often the countdown checks for cancellation
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+/// Source of timer generations
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Generation of the running countdown (0 when idle); replacing it cancels the old one
+static ACTIVE_TIMER: AtomicU64 = AtomicU64::new(0);
+
+/// What to capture when the countdown ends
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DelayedCaptureMode {
+    /// Monitor under the cursor when the timer fires
+    Fullscreen,
+    /// Freeze the screen and open the region selection overlay
+    Region,
+    Monitor { monitor_id: u32 },
+    Window { window_id: u32 },
+}
+
+/// Countdown tick payload
+#[derive(Debug, Clone, Serialize)]
+pub struct CountdownTick {
+    pub remaining: u32,
+    pub total: u32,
+}
+
+fn is_current(generation: u64) -> bool {
+    ACTIVE_TIMER.load(Ordering::SeqCst) == generation
+}
+
+/// Sleep one second, returning false if the timer was cancelled meanwhile
+fn wait_one_second(generation: u64) -> bool {
+    for _ in 0..10 {
+        if !is_current(generation) {
+            return false;
+        }
+        thread::sleep(CANCEL_POLL);
+    }
+    is_current(generation)
+}
+
+/// Perform the capture for a finished countdown
+fn capture_now(app: &AppHandle, mode: DelayedCaptureMode) -> Result<Option<CaptureRef>, String> {
+    let store = app.state::<CaptureStore>();
+    match mode {
+        DelayedCaptureMode::Fullscreen => match screenshot::get_cursor_monitor() {
+            Ok(monitor) => screenshot::capture_monitor_ref(app.clone(), store, monitor.id).map(Some),
+            Err(_) => screenshot::capture_fullscreen_ref(app.clone(), store).map(Some),
+        },
+        DelayedCaptureMode::Monitor { monitor_id } => {
+            screenshot::capture_monitor_ref(app.clone(), store, monitor_id).map(Some)
+        }
+        DelayedCaptureMode::Window { window_id } => {
+            screenshot::capture_window_ref(app.clone(), store, window_id, None).map(Some)
+        }
+        DelayedCaptureMode::Region => {
+            // Overlay reports back through region-selected / region-selection-cancelled
+            tauri::async_runtime::block_on(overlay::capture_and_show_overlay(app.clone()))?;
+            Ok(None)
+        }
+    }
+}
+
+/// Start a timed capture, replacing any countdown already running
+pub fn start(app: &AppHandle, mode: DelayedCaptureMode, seconds: u32) -> Result<(), String> {
+    if seconds == 0 || seconds > MAX_DELAY_SECONDS {
+        return Err(format!("Delay must be between 1 and {} seconds", MAX_DELAY_SECONDS));
+    }
+
+    let generation = NEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+    ACTIVE_TIMER.store(generation, Ordering::SeqCst);
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for remaining in (1..=seconds).rev() {
+            let _ = app.emit(
+                "delayed-capture-tick",
+                CountdownTick {
+                    remaining,
+                    total: seconds,
+                },
+            );
+            tray::set_tooltip(&app, Some(&format!("Capturing in {}s", remaining)));
+
+            if !wait_one_second(generation) {
+                return;
+            }
+        }
+
+        // Claim the capture; fails if cancelled or replaced at the last moment
+        if ACTIVE_TIMER
+            .compare_exchange(generation, 0, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+        tray::set_tooltip(&app, None);
+        println!("Timed capture firing: {:?}", mode);
+
+        match capture_now(&app, mode) {
+            Ok(Some(capture_ref)) => {
+                let _ = app.emit_to("main", "delayed-capture-complete", &capture_ref);
+                tray::show_main_window(&app);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let _ = app.emit_to("main", "delayed-capture-failed", &e);
+                tray::show_main_window(&app);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Cancel the running countdown; returns false if none was running
+pub fn cancel(app: &AppHandle) -> bool {
+    let running = ACTIVE_TIMER.swap(0, Ordering::SeqCst) != 0;
+    if running {
+        tray::set_tooltip(app, None);
+        let _ = app.emit("delayed-capture-cancelled", ());
+        tray::show_main_window(app);
+    }
+    running
+}
+
+/// Capture after a delay of `seconds`
+#[tauri::command]
+pub fn capture_after_delay(app: AppHandle, mode: DelayedCaptureMode, seconds: u32) -> Result<(), String> {
+    start(&app, mode, seconds)
+}
+
+/// Cancel a pending timed capture
+#[tauri::command]
+pub fn cancel_delayed_capture(app: AppHandle) -> bool {
+    cancel(&app)
+}
@@ -2,19 +2,99 @@
 
 use std::sync::atomic::Ordering;
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, Runtime,
 };
 
+use crate::interval_capture::{self, IntervalCaptureOptions};
+use crate::recording;
+use crate::timed_capture::{self, DelayedCaptureMode};
 use crate::SHOULD_QUIT;
 
+/// Id of the app's tray icon
+const TRAY_ID: &str = "main";
+
+/// Default tray tooltip
+const TRAY_TOOLTIP: &str = "beautiFULLshot";
+
+/// Show, restore and focus the main window
+pub fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+    // On macOS, restore dock icon before showing window
+    #[cfg(target_os = "macos")]
+    let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Emit a tray event to the main window
+pub fn emit_to_main<R: Runtime>(app: &AppHandle<R>, event: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, ());
+    }
+}
+
+/// Set the tray tooltip, or restore the default with `None`
+pub fn set_tooltip<R: Runtime>(app: &AppHandle<R>, text: Option<&str>) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(text.unwrap_or(TRAY_TOOLTIP)));
+    }
+}
+
+/// Show or clear the recording indicator (tray title where supported, plus tooltip)
+pub fn set_recording_indicator<R: Runtime>(app: &AppHandle<R>, recording: bool) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_title(recording.then_some("● REC"));
+        let _ = tray.set_tooltip(Some(if recording { "Recording…" } else { TRAY_TOOLTIP }));
+    }
+}
+
+/// Start a timed fullscreen capture from the tray menu
+fn start_timed_capture(app: &AppHandle, seconds: u32) {
+    if let Err(e) = timed_capture::start(app, DelayedCaptureMode::Fullscreen, seconds) {
+        println!("Failed to start timed capture: {}", e);
+    }
+}
+
+/// Start an interval capture session from the tray menu
+fn start_interval_capture(app: &AppHandle, options: IntervalCaptureOptions) {
+    match interval_capture::start(app, options) {
+        Ok(folder) => println!("Interval capture started: {}", folder),
+        Err(e) => println!("Failed to start interval capture: {}", e),
+    }
+}
+
 /// Creates and configures the system tray icon with menu
-pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
     // Capture menu items
     let capture_screen = MenuItem::with_id(app, "capture_screen", "Capture Screen", true, None::<&str>)?;
     let capture_region = MenuItem::with_id(app, "capture_region", "Capture Region", true, None::<&str>)?;
     let capture_window = MenuItem::with_id(app, "capture_window", "Capture Window", true, None::<&str>)?;
+    let capture_delay_3 = MenuItem::with_id(app, "capture_delay_3", "Capture in 3s", true, None::<&str>)?;
+    let capture_delay_5 = MenuItem::with_id(app, "capture_delay_5", "Capture in 5s", true, None::<&str>)?;
+    let capture_delay_10 = MenuItem::with_id(app, "capture_delay_10", "Capture in 10s", true, None::<&str>)?;
+    let cancel_delay = MenuItem::with_id(app, "cancel_delay", "Cancel Timed Capture", true, None::<&str>)?;
+    let timed_menu = Submenu::with_items(app, "Timed Capture", true, &[
+        &capture_delay_3,
+        &capture_delay_5,
+        &capture_delay_10,
+        &PredefinedMenuItem::separator(app)?,
+        &cancel_delay,
+    ])?;
+    let interval_5s = MenuItem::with_id(app, "interval_5s", "Every 5s for 10 min", true, None::<&str>)?;
+    let burst_10 = MenuItem::with_id(app, "burst_10", "Burst 10 Frames", true, None::<&str>)?;
+    let stop_interval = MenuItem::with_id(app, "stop_interval", "Stop Interval Capture", true, None::<&str>)?;
+    let interval_menu = Submenu::with_items(app, "Interval Capture", true, &[
+        &interval_5s,
+        &burst_10,
+        &PredefinedMenuItem::separator(app)?,
+        &stop_interval,
+    ])?;
+    let stop_recording = MenuItem::with_id(app, "stop_recording", "Stop Recording", true, None::<&str>)?;
     let separator1 = PredefinedMenuItem::separator(app)?;
     let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
     let separator2 = PredefinedMenuItem::separator(app)?;
@@ -25,6 +105,9 @@ pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
         &capture_screen,
         &capture_region,
         &capture_window,
+        &timed_menu,
+        &interval_menu,
+        &stop_recording,
         &separator1,
         &show_item,
         &separator2,
@@ -38,48 +121,40 @@ pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
         .ok_or_else(|| tauri::Error::AssetNotFound("default window icon".to_string()))?;
 
     // Build tray icon
-    let _tray = TrayIconBuilder::<R>::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon.clone())
         .menu(&menu)
         .show_menu_on_left_click(false)
-        .tooltip("beautiFULLshot")
+        .tooltip(TRAY_TOOLTIP)
         // macOS: don't use template mode so colorful icons display correctly
         .icon_as_template(false)
-        .on_menu_event(|app: &AppHandle<R>, event| match event.id.as_ref() {
+        .on_menu_event(|app: &AppHandle, event| match event.id.as_ref() {
             "quit" => {
                 // Set flag so ExitRequested handler allows quit
                 SHOULD_QUIT.store(true, Ordering::SeqCst);
                 app.exit(0);
             }
-            "show" => {
-                // On macOS, restore dock icon before showing window
-                #[cfg(target_os = "macos")]
-                let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
-
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.unminimize();
-                    let _ = window.set_focus();
-                }
-            }
-            "capture_screen" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("tray-capture-screen", ());
-                }
+            "show" => show_main_window(app),
+            "capture_screen" => emit_to_main(app, "tray-capture-screen"),
+            "capture_region" => emit_to_main(app, "tray-capture-region"),
+            "capture_window" => emit_to_main(app, "tray-capture-window"),
+            "capture_delay_3" => start_timed_capture(app, 3),
+            "capture_delay_5" => start_timed_capture(app, 5),
+            "capture_delay_10" => start_timed_capture(app, 10),
+            "cancel_delay" => {
+                timed_capture::cancel(app);
             }
-            "capture_region" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("tray-capture-region", ());
-                }
+            "interval_5s" => start_interval_capture(app, IntervalCaptureOptions::every(5, 10)),
+            "burst_10" => start_interval_capture(app, IntervalCaptureOptions::burst(10, 200)),
+            "stop_interval" => {
+                interval_capture::stop();
             }
-            "capture_window" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("tray-capture-window", ());
-                }
+            "stop_recording" => {
+                recording::stop();
             }
             _ => {}
         })
-        .on_tray_icon_event(|tray: &TrayIcon<R>, event| {
+        .on_tray_icon_event(|tray: &TrayIcon, event| {
             // Left click on tray icon shows window
             if let TrayIconEvent::Click {
                 button: MouseButton::Left,
@@ -87,17 +162,7 @@ pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
                 ..
             } = event
             {
-                let app = tray.app_handle();
-
-                // On macOS, restore dock icon before showing window
-                #[cfg(target_os = "macos")]
-                let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
-
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.unminimize();
-                    let _ = window.set_focus();
-                }
+                show_main_window(tray.app_handle());
             }
         })
         .build(app)?;
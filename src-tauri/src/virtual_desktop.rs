@@ -0,0 +1,342 @@
+// Virtual desktop capture
+// Captures every monitor on its own thread and composes them into one canvas laid
+// out by desktop position, so a capture or region can span display boundaries.
+// Monitor geometry is in desktop units (physical pixels on Windows/X11, points on
+// macOS); with mixed DPI every capture is resampled to one pixels-per-unit scale.
+// Gaps between monitors in non-rectangular layouts get a configurable fill.
+
+use std::thread;
+
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::capture_backend::{self, Capture};
+use crate::capture_store::{CaptureKind, CaptureMetadata, CaptureRef, CaptureStore};
+use crate::screenshot::{self, crop_region, image_to_base64_png, MonitorInfo};
+use crate::{history, naming};
+
+/// What fills desktop areas no monitor covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapFill {
+    #[default]
+    Transparent,
+    Black,
+    /// The desktop background color from system settings (black when unknown)
+    Desktop,
+}
+
+impl GapFill {
+    /// Parse a fill name as used on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "transparent" => Some(Self::Transparent),
+            "black" => Some(Self::Black),
+            "desktop" => Some(Self::Desktop),
+            _ => None,
+        }
+    }
+
+    fn color(self) -> Rgba<u8> {
+        match self {
+            Self::Transparent => Rgba([0, 0, 0, 0]),
+            Self::Black => Rgba([0, 0, 0, 255]),
+            Self::Desktop => desktop_color().unwrap_or(Rgba([0, 0, 0, 255])),
+        }
+    }
+}
+
+/// Which pixel density mixed-DPI captures are resampled to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DpiScale {
+    /// Upscale lower-density monitors so no display loses detail
+    #[default]
+    Highest,
+    /// Downscale higher-density monitors for a smaller image
+    Lowest,
+}
+
+impl DpiScale {
+    /// Parse a scale name as used on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "highest" => Some(Self::Highest),
+            "lowest" => Some(Self::Lowest),
+            _ => None,
+        }
+    }
+}
+
+/// Options for composing monitors into one image
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesktopCaptureOptions {
+    pub gap_fill: GapFill,
+    pub dpi: DpiScale,
+}
+
+/// A monitor's place on the desktop and on the composed canvas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopMonitor {
+    pub id: u32,
+    pub name: String,
+    /// Desktop position and size (same units as `MonitorInfo`)
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Position and size on the canvas, in canvas pixels
+    pub canvas_x: u32,
+    pub canvas_y: u32,
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+}
+
+/// How desktop coordinates map onto the composed canvas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopLayout {
+    /// Desktop origin (top-left of the bounding box of all monitors)
+    pub x: i32,
+    pub y: i32,
+    /// Canvas pixels per desktop unit
+    pub scale: f64,
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+    pub monitors: Vec<DesktopMonitor>,
+}
+
+impl DesktopLayout {
+    /// Lay monitors out by their desktop offsets at the given canvas scale
+    fn new(monitors: &[MonitorInfo], scale: f64) -> Result<Self, String> {
+        let min_x = monitors.iter().map(|m| m.x).min().ok_or("No monitors found")?;
+        let min_y = monitors.iter().map(|m| m.y).min().ok_or("No monitors found")?;
+        let max_x = monitors.iter().map(|m| m.x + m.width as i32).max().unwrap_or(min_x);
+        let max_y = monitors.iter().map(|m| m.y + m.height as i32).max().unwrap_or(min_y);
+
+        let to_canvas = |units: i32| (units as f64 * scale).round() as u32;
+        Ok(Self {
+            x: min_x,
+            y: min_y,
+            scale,
+            canvas_width: to_canvas(max_x - min_x),
+            canvas_height: to_canvas(max_y - min_y),
+            monitors: monitors
+                .iter()
+                .map(|m| DesktopMonitor {
+                    id: m.id,
+                    name: m.name.clone(),
+                    x: m.x,
+                    y: m.y,
+                    width: m.width,
+                    height: m.height,
+                    canvas_x: to_canvas(m.x - min_x),
+                    canvas_y: to_canvas(m.y - min_y),
+                    canvas_width: to_canvas(m.width as i32),
+                    canvas_height: to_canvas(m.height as i32),
+                })
+                .collect(),
+        })
+    }
+
+    /// Convert a desktop rectangle to canvas pixels
+    pub fn to_canvas_rect(&self, x: i32, y: i32, width: u32, height: u32) -> (i32, i32, u32, u32) {
+        (
+            ((x - self.x) as f64 * self.scale).round() as i32,
+            ((y - self.y) as f64 * self.scale).round() as i32,
+            (width as f64 * self.scale).round() as u32,
+            (height as f64 * self.scale).round() as u32,
+        )
+    }
+}
+
+/// The composed desktop image and the layout it was drawn with
+pub struct DesktopCapture {
+    pub image: RgbaImage,
+    pub layout: DesktopLayout,
+    pub backend: &'static str,
+}
+
+/// Desktop background color from system settings
+#[cfg(target_os = "windows")]
+fn desktop_color() -> Option<Rgba<u8>> {
+    use windows::Win32::Graphics::Gdi::{GetSysColor, COLOR_DESKTOP};
+    // COLORREF is 0x00BBGGRR
+    let color = unsafe { GetSysColor(COLOR_DESKTOP) };
+    Some(Rgba([
+        (color & 0xff) as u8,
+        ((color >> 8) & 0xff) as u8,
+        ((color >> 16) & 0xff) as u8,
+        255,
+    ]))
+}
+
+/// Desktop background color from system settings (GNOME primary color)
+#[cfg(target_os = "linux")]
+fn desktop_color() -> Option<Rgba<u8>> {
+    let output = std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.background", "primary-color"])
+        .output()
+        .ok()?;
+    // Printed as a quoted hex string, e.g. '#023c88'
+    let value = String::from_utf8_lossy(&output.stdout);
+    let hex = value.trim().trim_matches('\'').strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255]))
+}
+
+/// Desktop background color from system settings (not exposed on this platform)
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn desktop_color() -> Option<Rgba<u8>> {
+    None
+}
+
+/// Capture the given monitors concurrently, one thread per monitor
+/// Results are in the same order as `monitors`
+pub(crate) fn capture_monitors(monitors: &[MonitorInfo]) -> Vec<Result<Capture, String>> {
+    let registry = capture_backend::registry();
+    thread::scope(|scope| {
+        let handles: Vec<_> = monitors
+            .iter()
+            .map(|monitor| scope.spawn(move || registry.capture_monitor(Some(monitor.id))))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err("Capture thread panicked".to_string()))
+            })
+            .collect()
+    })
+}
+
+/// Draw each monitor capture at its canvas position, resampling captures whose
+/// pixel density differs from the canvas
+fn compose(layout: &DesktopLayout, captures: &[(usize, RgbaImage)], fill: GapFill) -> RgbaImage {
+    let mut canvas = RgbaImage::from_pixel(layout.canvas_width, layout.canvas_height, fill.color());
+    for (index, image) in captures {
+        let monitor = &layout.monitors[*index];
+        if image.width() == monitor.canvas_width && image.height() == monitor.canvas_height {
+            imageops::replace(&mut canvas, image, monitor.canvas_x as i64, monitor.canvas_y as i64);
+        } else {
+            let scaled = imageops::resize(
+                image,
+                monitor.canvas_width,
+                monitor.canvas_height,
+                FilterType::CatmullRom,
+            );
+            imageops::replace(&mut canvas, &scaled, monitor.canvas_x as i64, monitor.canvas_y as i64);
+        }
+    }
+    canvas
+}
+
+/// Capture every monitor and compose them into one desktop image
+/// Monitors that fail to capture are filled like gaps; fails only when none succeed
+pub fn capture_desktop(options: &DesktopCaptureOptions) -> Result<DesktopCapture, String> {
+    let monitors = screenshot::get_monitors()?;
+
+    let mut captures = Vec::new();
+    let mut backend = "";
+    let mut last_error = None;
+    for (index, result) in capture_monitors(&monitors).into_iter().enumerate() {
+        match result {
+            Ok(capture) => {
+                backend = capture.backend;
+                captures.push((index, capture.image));
+            }
+            Err(e) => {
+                let monitor = &monitors[index];
+                println!("Failed to capture monitor {} ({}): {}", monitor.id, monitor.name, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    if captures.is_empty() {
+        return Err(last_error.unwrap_or_else(|| "No monitors found".to_string()));
+    }
+
+    // Canvas pixels per desktop unit, from the densest or least dense monitor
+    let densities = captures
+        .iter()
+        .map(|(index, image)| image.width() as f64 / monitors[*index].width.max(1) as f64);
+    let scale = match options.dpi {
+        DpiScale::Highest => densities.fold(f64::MIN, f64::max),
+        DpiScale::Lowest => densities.fold(f64::MAX, f64::min),
+    };
+
+    let layout = DesktopLayout::new(&monitors, scale)?;
+    let image = compose(&layout, &captures, options.gap_fill);
+    Ok(DesktopCapture {
+        image,
+        layout,
+        backend,
+    })
+}
+
+/// Describe a desktop image (or a region cut from it) for the capture store
+pub(crate) fn desktop_metadata(kind: CaptureKind, backend: &str, layout: &DesktopLayout) -> CaptureMetadata {
+    let mut metadata = CaptureMetadata::new(kind, backend);
+    metadata.scale_factor = layout.scale as f32;
+    if let Some((x, y)) = screenshot::get_cursor_position() {
+        metadata.cursor_x = Some(x);
+        metadata.cursor_y = Some(y);
+    }
+    metadata
+}
+
+/// Get the layout the desktop image would be composed with (no capture)
+/// Assumes one pixel per desktop unit; the capture reports the real scale
+#[tauri::command]
+pub fn get_desktop_layout() -> Result<DesktopLayout, String> {
+    DesktopLayout::new(&screenshot::get_monitors()?, 1.0)
+}
+
+/// Capture a region in desktop coordinates into the capture store
+/// The region may span several monitors
+#[tauri::command]
+pub fn capture_desktop_region_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<CaptureRef, String> {
+    let desktop = capture_desktop(&DesktopCaptureOptions::default())?;
+    let (cx, cy, cw, ch) = desktop.layout.to_canvas_rect(x, y, width, height);
+    let cropped = crop_region(&desktop.image, cx, cy, cw, ch)?;
+    let metadata = desktop_metadata(CaptureKind::Region, desktop.backend, &desktop.layout);
+    let capture_ref = store.insert(cropped, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
+}
+
+/// Capture all monitors stitched into one image - returns base64-encoded PNG
+#[tauri::command]
+pub fn capture_all_screens(options: Option<DesktopCaptureOptions>) -> Result<String, String> {
+    let desktop = capture_desktop(&options.unwrap_or_default())?;
+    image_to_base64_png(&desktop.image)
+}
+
+/// Capture all monitors stitched into one image into the capture store
+#[tauri::command]
+pub fn capture_all_screens_ref(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    options: Option<DesktopCaptureOptions>,
+) -> Result<CaptureRef, String> {
+    let desktop = capture_desktop(&options.unwrap_or_default())?;
+    let metadata = desktop_metadata(CaptureKind::Monitor, desktop.backend, &desktop.layout);
+    let capture_ref = store.insert(desktop.image, metadata);
+    history::auto_record(&app, &capture_ref);
+    naming::auto_save(&app, &capture_ref);
+    Ok(capture_ref)
+}
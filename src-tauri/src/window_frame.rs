@@ -0,0 +1,364 @@
+// Window capture framing
+// xcap returns different pixels per platform for the same window: the client area
+// on X11, the frame bounds on macOS and the full window rect (with invisible resize
+// borders) on Windows. These options normalize captures to content only, frame, or
+// frame with a rendered drop shadow, so screenshots match across machines
+
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use xcap::Window as XcapWindow;
+
+use crate::capture_backend::{self, Capture};
+use crate::screenshot::crop_region;
+
+/// Drop shadow blur radius in logical pixels
+const SHADOW_RADIUS: f32 = 20.0;
+/// Drop shadow downward offset in logical pixels
+const SHADOW_OFFSET_Y: f32 = 10.0;
+/// Peak shadow opacity (0-255)
+const SHADOW_OPACITY: u8 = 110;
+
+/// How much of the window a capture includes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowFraming {
+    /// Whatever the platform capture returns
+    #[default]
+    Native,
+    /// Client area only: no title bar, borders or client-side shadow
+    Content,
+    /// Client area plus the title bar and borders
+    Frame,
+    /// Frame with a drop shadow rendered around it
+    Shadow,
+}
+
+impl WindowFraming {
+    /// Parse a CLI name ("native", "content", "frame", "shadow")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "native" => Some(Self::Native),
+            "content" => Some(Self::Content),
+            "frame" => Some(Self::Frame),
+            "shadow" => Some(Self::Shadow),
+            _ => None,
+        }
+    }
+}
+
+/// Window capture settings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowCaptureOptions {
+    pub framing: WindowFraming,
+    /// Keep the area around the window (shadow margin, rounded corners) transparent
+    /// instead of flattening it onto white
+    pub transparent: bool,
+}
+
+impl Default for WindowCaptureOptions {
+    fn default() -> Self {
+        Self {
+            framing: WindowFraming::Native,
+            transparent: true,
+        }
+    }
+}
+
+/// Rectangle in pixels of the native window capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Display scale of the monitor a window is on, for sizing the shadow
+fn window_scale_factor(window_id: u32) -> f32 {
+    XcapWindow::all()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|w| w.id().unwrap_or(0) == window_id)
+        .and_then(|w| w.current_monitor().ok())
+        .and_then(|m| m.scale_factor().ok())
+        .unwrap_or(1.0)
+}
+
+/// Capture a window with the requested framing
+pub fn capture(window_id: u32, options: &WindowCaptureOptions) -> Result<Capture, String> {
+    let native = capture_backend::registry().capture_window(window_id)?;
+    let backend = native.backend;
+
+    let image = match options.framing {
+        WindowFraming::Native => native.image,
+        WindowFraming::Content => match platform::content_rect(window_id, &native.image) {
+            Some(rect) => crop(&native.image, rect)?,
+            None => native.image,
+        },
+        WindowFraming::Frame => platform::frame(window_id, native.image)?,
+        WindowFraming::Shadow => {
+            let frame = platform::frame(window_id, native.image)?;
+            add_shadow(&frame, window_scale_factor(window_id))
+        }
+    };
+
+    let image = if options.transparent { image } else { flatten(image) };
+    Ok(Capture { image, backend })
+}
+
+fn crop(image: &RgbaImage, rect: Rect) -> Result<RgbaImage, String> {
+    crop_region(image, rect.x, rect.y, rect.width, rect.height)
+}
+
+/// Render a soft drop shadow behind the window, following its alpha (rounded corners)
+fn add_shadow(window: &RgbaImage, scale_factor: f32) -> RgbaImage {
+    let scale = scale_factor.max(1.0);
+    let radius = SHADOW_RADIUS * scale;
+    let offset_y = (SHADOW_OFFSET_Y * scale).round() as u32;
+    let margin = (radius * 2.0).ceil() as u32;
+
+    let width = window.width() + margin * 2;
+    let height = window.height() + margin * 2 + offset_y;
+
+    // Blur a silhouette of the window, shifted down by the offset
+    let mut mask = GrayImage::new(width, height);
+    for (x, y, pixel) in window.enumerate_pixels() {
+        let alpha = pixel[3] as u32 * SHADOW_OPACITY as u32 / 255;
+        mask.put_pixel(x + margin, y + margin + offset_y, Luma([alpha as u8]));
+    }
+    let mask = image::imageops::fast_blur(&mask, radius / 2.0);
+
+    let mut canvas = RgbaImage::from_fn(width, height, |x, y| Rgba([0, 0, 0, mask.get_pixel(x, y)[0]]));
+    image::imageops::overlay(&mut canvas, window, margin as i64, margin as i64);
+    canvas
+}
+
+/// Composite onto an opaque white background
+fn flatten(mut image: RgbaImage) -> RgbaImage {
+    for pixel in image.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        for channel in 0..3 {
+            pixel[channel] = ((pixel[channel] as u32 * alpha + 255 * (255 - alpha)) / 255) as u8;
+        }
+        pixel[3] = 255;
+    }
+    image
+}
+
+/// X11: xcap captures the client window, so the frame comes from the screen using
+/// the window manager's `_NET_FRAME_EXTENTS`; GTK client-side decorations draw their
+/// shadow inside the client window and report it through `_GTK_FRAME_EXTENTS`
+#[cfg(target_os = "linux")]
+mod platform {
+    use image::RgbaImage;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, Window};
+    use x11rb::rust_connection::RustConnection;
+
+    use super::{crop, Rect};
+    use crate::capture_backend::{self, is_wayland};
+    use crate::screenshot::crop_region;
+
+    /// Border widths in pixels (left, right, top, bottom)
+    #[derive(Debug, Clone, Copy, Default)]
+    struct Extents {
+        left: u32,
+        right: u32,
+        top: u32,
+        bottom: u32,
+    }
+
+    /// X11 geometry of a client window
+    struct ClientGeometry {
+        /// Root-relative position of the client window
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        /// Decorations drawn by the window manager around the client window
+        frame: Extents,
+        /// Shadow margins drawn by the client inside its window (GTK CSD)
+        csd_shadow: Extents,
+    }
+
+    impl ClientGeometry {
+        /// Area inside the client window excluding the CSD shadow
+        fn visible_rect(&self) -> Rect {
+            Rect {
+                x: self.csd_shadow.left as i32,
+                y: self.csd_shadow.top as i32,
+                width: self
+                    .width
+                    .saturating_sub(self.csd_shadow.left + self.csd_shadow.right),
+                height: self
+                    .height
+                    .saturating_sub(self.csd_shadow.top + self.csd_shadow.bottom),
+            }
+        }
+    }
+
+    fn read_extents(conn: &RustConnection, window: Window, name: &[u8]) -> Result<Option<Extents>, String> {
+        let atom = conn
+            .intern_atom(true, name)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?
+            .atom;
+        if atom == x11rb::NONE {
+            return Ok(None);
+        }
+        let reply = conn
+            .get_property(false, window, atom, AtomEnum::CARDINAL, 0, 4)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?;
+        let values: Vec<u32> = reply.value32().map(|v| v.collect()).unwrap_or_default();
+        Ok(match values[..] {
+            [left, right, top, bottom] => Some(Extents { left, right, top, bottom }),
+            _ => None,
+        })
+    }
+
+    fn client_geometry(window_id: u32) -> Result<ClientGeometry, String> {
+        let (conn, _) = x11rb::connect(None).map_err(|e| e.to_string())?;
+        let geometry = conn
+            .get_geometry(window_id)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?;
+        let origin = conn
+            .translate_coordinates(window_id, geometry.root, 0, 0)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?;
+
+        Ok(ClientGeometry {
+            x: origin.dst_x as i32,
+            y: origin.dst_y as i32,
+            width: geometry.width as u32,
+            height: geometry.height as u32,
+            frame: read_extents(&conn, window_id, b"_NET_FRAME_EXTENTS")?.unwrap_or_default(),
+            csd_shadow: read_extents(&conn, window_id, b"_GTK_FRAME_EXTENTS")?.unwrap_or_default(),
+        })
+    }
+
+    /// Wayland hides window geometry from clients, so captures stay native there
+    fn x11_geometry(window_id: u32) -> Option<ClientGeometry> {
+        if is_wayland() {
+            return None;
+        }
+        client_geometry(window_id)
+            .map_err(|e| println!("Failed to read X11 window geometry: {}", e))
+            .ok()
+    }
+
+    pub(super) fn content_rect(window_id: u32, _image: &RgbaImage) -> Option<Rect> {
+        x11_geometry(window_id).map(|geometry| geometry.visible_rect())
+    }
+
+    pub(super) fn frame(window_id: u32, image: RgbaImage) -> Result<RgbaImage, String> {
+        let Some(geometry) = x11_geometry(window_id) else {
+            return Ok(image);
+        };
+        let frame = geometry.frame;
+        if frame.left + frame.right + frame.top + frame.bottom == 0 {
+            // Undecorated or client-side decorated: the frame is inside the client window
+            return crop(&image, geometry.visible_rect());
+        }
+
+        // Decorations belong to the window manager's parent window; take them from the screen
+        let monitor = xcap::Monitor::from_point(geometry.x, geometry.y).map_err(|e| e.to_string())?;
+        let monitor_x = monitor.x().map_err(|e| e.to_string())?;
+        let monitor_y = monitor.y().map_err(|e| e.to_string())?;
+        let screen = capture_backend::registry()
+            .capture_monitor(Some(monitor.id().map_err(|e| e.to_string())?))?
+            .image;
+        crop_region(
+            &screen,
+            geometry.x - frame.left as i32 - monitor_x,
+            geometry.y - frame.top as i32 - monitor_y,
+            geometry.width + frame.left + frame.right,
+            geometry.height + frame.top + frame.bottom,
+        )
+    }
+}
+
+/// Windows: the capture covers the window rect, which includes invisible resize
+/// borders; DWM's extended frame bounds are the visible frame
+#[cfg(target_os = "windows")]
+mod platform {
+    use image::RgbaImage;
+    use windows::Win32::Foundation::{HWND, RECT};
+    use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+    use windows::Win32::UI::WindowsAndMessaging::{GetWindowInfo, WINDOWINFO};
+
+    use super::{crop, Rect};
+
+    /// Map a screen rect into the capture, which may be scaled for DPI-unaware windows
+    fn to_image_rect(rect: RECT, window: RECT, image: &RgbaImage) -> Rect {
+        let scale_x = image.width() as f32 / (window.right - window.left).max(1) as f32;
+        let scale_y = image.height() as f32 / (window.bottom - window.top).max(1) as f32;
+        Rect {
+            x: ((rect.left - window.left) as f32 * scale_x).round() as i32,
+            y: ((rect.top - window.top) as f32 * scale_y).round() as i32,
+            width: ((rect.right - rect.left).max(0) as f32 * scale_x).round() as u32,
+            height: ((rect.bottom - rect.top).max(0) as f32 * scale_y).round() as u32,
+        }
+    }
+
+    fn window_info(hwnd: HWND) -> Option<WINDOWINFO> {
+        let mut info = WINDOWINFO {
+            cbSize: std::mem::size_of::<WINDOWINFO>() as u32,
+            ..Default::default()
+        };
+        unsafe { GetWindowInfo(hwnd, &mut info) }.ok()?;
+        Some(info)
+    }
+
+    fn hwnd(window_id: u32) -> HWND {
+        HWND(window_id as usize as *mut _)
+    }
+
+    pub(super) fn content_rect(window_id: u32, image: &RgbaImage) -> Option<Rect> {
+        let info = window_info(hwnd(window_id))?;
+        Some(to_image_rect(info.rcClient, info.rcWindow, image))
+    }
+
+    pub(super) fn frame(window_id: u32, image: RgbaImage) -> Result<RgbaImage, String> {
+        let hwnd = hwnd(window_id);
+        let Some(info) = window_info(hwnd) else {
+            return Ok(image);
+        };
+        let mut bounds = RECT::default();
+        let result = unsafe {
+            DwmGetWindowAttribute(
+                hwnd,
+                DWMWA_EXTENDED_FRAME_BOUNDS,
+                &mut bounds as *mut RECT as *mut _,
+                std::mem::size_of::<RECT>() as u32,
+            )
+        };
+        if result.is_err() {
+            return Ok(image);
+        }
+        crop(&image, to_image_rect(bounds, info.rcWindow, &image))
+    }
+}
+
+/// macOS: the capture is the window's frame bounds without the system shadow;
+/// the content area is not exposed for other apps' windows, so it keeps the frame
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+mod platform {
+    use image::RgbaImage;
+
+    use super::Rect;
+
+    pub(super) fn content_rect(_window_id: u32, _image: &RgbaImage) -> Option<Rect> {
+        println!("Content-only window capture is not supported on this platform; keeping the frame");
+        None
+    }
+
+    pub(super) fn frame(_window_id: u32, image: RgbaImage) -> Result<RgbaImage, String> {
+        Ok(image)
+    }
+}
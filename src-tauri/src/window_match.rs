@@ -0,0 +1,291 @@
+// Window lookup by title, app name, process, focus and pointer position
+// Lets scripts (CLI) and global shortcuts target a window without its numeric id
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::capture_store::{CaptureRef, CaptureStore};
+use crate::screenshot::{self, WindowInfo};
+use crate::window_frame::WindowCaptureOptions;
+
+/// Which windows to match; empty fields match any window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowQuery {
+    /// Pattern matched against the window title
+    pub title: Option<String>,
+    /// Pattern matched against the application name
+    pub app_name: Option<String>,
+    /// Treat patterns as regular expressions instead of substrings (both case-insensitive)
+    pub regex: bool,
+    /// Only windows owned by this process
+    pub pid: Option<u32>,
+    /// Only the focused window (`true`) or only background windows (`false`)
+    pub focused: Option<bool>,
+}
+
+impl WindowQuery {
+    /// Whether any criterion is set
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.app_name.is_none() && self.pid.is_none() && self.focused.is_none()
+    }
+}
+
+/// How closely a pattern matched; higher ranks first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchQuality {
+    /// No pattern given for this field
+    Any,
+    Contains,
+    Prefix,
+    Exact,
+}
+
+enum Pattern {
+    /// Lowercased needle
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    fn new(pattern: &str, regex: bool) -> Result<Self, String> {
+        if regex {
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map(Pattern::Regex)
+                .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+        } else {
+            Ok(Pattern::Substring(pattern.to_lowercase()))
+        }
+    }
+
+    fn quality(&self, text: &str) -> Option<MatchQuality> {
+        match self {
+            Pattern::Substring(needle) => {
+                let text = text.to_lowercase();
+                if text == *needle {
+                    Some(MatchQuality::Exact)
+                } else if text.starts_with(needle.as_str()) {
+                    Some(MatchQuality::Prefix)
+                } else if text.contains(needle.as_str()) {
+                    Some(MatchQuality::Contains)
+                } else {
+                    None
+                }
+            }
+            Pattern::Regex(regex) => {
+                let found = regex.find(text)?;
+                Some(if found.start() == 0 && found.end() == text.len() {
+                    MatchQuality::Exact
+                } else if found.start() == 0 {
+                    MatchQuality::Prefix
+                } else {
+                    MatchQuality::Contains
+                })
+            }
+        }
+    }
+}
+
+/// Match a field against an optional pattern
+fn field_quality(pattern: &Option<Pattern>, text: &str) -> Option<MatchQuality> {
+    match pattern {
+        Some(pattern) => pattern.quality(text),
+        None => Some(MatchQuality::Any),
+    }
+}
+
+/// Filter and rank windows: title match, then app name match, then focus,
+/// keeping the front-to-back stacking order for ties
+pub fn rank_windows(windows: Vec<WindowInfo>, query: &WindowQuery) -> Result<Vec<WindowInfo>, String> {
+    let title = query.title.as_deref().map(|p| Pattern::new(p, query.regex)).transpose()?;
+    let app_name = query.app_name.as_deref().map(|p| Pattern::new(p, query.regex)).transpose()?;
+
+    let mut ranked: Vec<_> = windows
+        .into_iter()
+        .filter(|w| query.pid.is_none_or(|pid| w.pid == pid))
+        .filter(|w| query.focused.is_none_or(|focused| w.is_focused == focused))
+        .filter_map(|w| {
+            let title_quality = field_quality(&title, &w.title)?;
+            let app_quality = field_quality(&app_name, &w.app_name)?;
+            Some(((title_quality, app_quality, w.is_focused), w))
+        })
+        .collect();
+
+    // Stable sort keeps stacking order among equal ranks
+    ranked.sort_by_key(|(rank, _)| std::cmp::Reverse(*rank));
+    Ok(ranked.into_iter().map(|(_, w)| w).collect())
+}
+
+/// Best-ranked window for a query
+pub fn best_match(query: &WindowQuery) -> Result<WindowInfo, String> {
+    let windows = screenshot::get_windows()?;
+    rank_windows(windows, query)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No matching window found".to_string())
+}
+
+/// Windows of other apps, front to back; our own editor and overlays are never targets
+pub(crate) fn other_app_windows() -> Result<Vec<WindowInfo>, String> {
+    let own_pid = std::process::id();
+    Ok(screenshot::get_windows()?
+        .into_iter()
+        .filter(|w| w.pid != own_pid && !w.is_minimized)
+        .collect())
+}
+
+/// The focused window, or the frontmost one when the platform reports no focus
+pub fn active_window() -> Result<WindowInfo, String> {
+    let windows = other_app_windows()?;
+    let focused = windows.iter().position(|w| w.is_focused).unwrap_or(0);
+    windows
+        .into_iter()
+        .nth(focused)
+        .ok_or_else(|| "No active window found".to_string())
+}
+
+/// The topmost window containing the mouse pointer
+pub fn window_under_cursor() -> Result<WindowInfo, String> {
+    let (x, y) = screenshot::get_cursor_position().ok_or("Failed to get cursor position")?;
+    other_app_windows()?
+        .into_iter()
+        .find(|w| {
+            x >= w.x && y >= w.y && x < w.x + w.width as i32 && y < w.y + w.height as i32
+        })
+        .ok_or_else(|| "No window found under the cursor".to_string())
+}
+
+/// List windows matching a query, best match first
+#[tauri::command]
+pub fn find_windows(query: WindowQuery) -> Result<Vec<WindowInfo>, String> {
+    rank_windows(screenshot::get_windows()?, &query)
+}
+
+/// Capture the best match for a query into the capture store
+#[tauri::command]
+pub fn capture_window_matching(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    query: WindowQuery,
+    options: Option<WindowCaptureOptions>,
+) -> Result<CaptureRef, String> {
+    let window = best_match(&query)?;
+    println!("Capturing window {} ({}: {})", window.id, window.app_name, window.title);
+    screenshot::capture_window_ref(app, store, window.id, options)
+}
+
+/// Capture the focused window into the capture store
+#[tauri::command]
+pub fn capture_active_window(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    options: Option<WindowCaptureOptions>,
+) -> Result<CaptureRef, String> {
+    let window = active_window()?;
+    println!("Capturing active window {} ({}: {})", window.id, window.app_name, window.title);
+    screenshot::capture_window_ref(app, store, window.id, options)
+}
+
+/// Capture the window under the mouse pointer into the capture store
+#[tauri::command]
+pub fn capture_window_under_cursor(
+    app: AppHandle,
+    store: State<'_, CaptureStore>,
+    options: Option<WindowCaptureOptions>,
+) -> Result<CaptureRef, String> {
+    let window = window_under_cursor()?;
+    println!("Capturing window under cursor {} ({}: {})", window.id, window.app_name, window.title);
+    screenshot::capture_window_ref(app, store, window.id, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: u32, app_name: &str, title: &str, is_focused: bool) -> WindowInfo {
+        WindowInfo {
+            id,
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            x: 0,
+            y: 0,
+            width: 800,
+            height: 600,
+            pid: id * 10,
+            is_focused,
+            is_minimized: false,
+        }
+    }
+
+    /// Front-to-back stack used by most tests
+    fn windows() -> Vec<WindowInfo> {
+        vec![
+            window(1, "Code", "main.rs - beautiFULLshot", false),
+            window(2, "Firefox", "Mozilla Firefox", true),
+            window(3, "Terminal", "Firefox build log", false),
+            window(4, "Firefox", "Firefox", false),
+        ]
+    }
+
+    fn ids(query: &WindowQuery) -> Vec<u32> {
+        rank_windows(windows(), query).unwrap().iter().map(|w| w.id).collect()
+    }
+
+    #[test]
+    fn empty_query_keeps_stacking_order_with_focus_first() {
+        let query = WindowQuery::default();
+        assert!(query.is_empty());
+        assert_eq!(ids(&query), vec![2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn title_ranks_exact_then_prefix_then_contains() {
+        let query = WindowQuery { title: Some("FIREFOX".to_string()), ..Default::default() };
+        assert_eq!(ids(&query), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn title_rank_outweighs_app_name_and_focus() {
+        let query = WindowQuery {
+            title: Some("firefox".to_string()),
+            app_name: Some("firefox".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query), vec![4, 2]);
+    }
+
+    #[test]
+    fn filters_by_pid_and_focus() {
+        let query = WindowQuery { pid: Some(30), ..Default::default() };
+        assert_eq!(ids(&query), vec![3]);
+
+        let query = WindowQuery { focused: Some(false), app_name: Some("fire".to_string()), ..Default::default() };
+        assert_eq!(ids(&query), vec![4]);
+    }
+
+    #[test]
+    fn regex_patterns_are_case_insensitive() {
+        let query = WindowQuery { title: Some(r"\.rs\b".to_string()), regex: true, ..Default::default() };
+        assert_eq!(ids(&query), vec![1]);
+
+        let query = WindowQuery { title: Some("^firefox$".to_string()), regex: true, ..Default::default() };
+        assert_eq!(ids(&query), vec![4]);
+    }
+
+    #[test]
+    fn regex_special_characters_are_literal_in_substring_mode() {
+        let query = WindowQuery { title: Some(".rs".to_string()), ..Default::default() };
+        assert_eq!(ids(&query), vec![1]);
+        let query = WindowQuery { title: Some("^fire".to_string()), ..Default::default() };
+        assert!(ids(&query).is_empty());
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        let query = WindowQuery { title: Some("(".to_string()), regex: true, ..Default::default() };
+        assert!(rank_windows(windows(), &query).is_err());
+    }
+}
@@ -95,6 +95,18 @@ export function MonitorPickerModal({ isOpen, onClose }: Props) {
     }
   };
 
+  // Select across all monitors - one overlay per monitor sharing the selection
+  const handleSelectAllMonitors = async () => {
+    try {
+      onClose();
+      // Small delay to let modal close animation complete
+      await new Promise((resolve) => setTimeout(resolve, 100));
+      await screenshotApi.showDesktopOverlay();
+    } catch (e) {
+      logError('MonitorPickerModal:selectAllMonitors', e);
+    }
+  };
+
   if (!isOpen) return null;
 
   return (
@@ -213,7 +225,14 @@ export function MonitorPickerModal({ isOpen, onClose }: Props) {
         </div>
 
         {/* Footer */}
-        <div className="p-4 border-t border-gray-200 dark:border-gray-700 flex justify-end">
+        <div className="p-4 border-t border-gray-200 dark:border-gray-700 flex justify-between">
+          <button
+            onClick={handleSelectAllMonitors}
+            disabled={loading || monitors.length === 0}
+            className="px-4 py-2 text-sm text-orange-600 dark:text-orange-400 hover:text-orange-700 dark:hover:text-orange-300 disabled:opacity-50"
+          >
+            Chọn vùng trên tất cả màn hình
+          </button>
           <button
             onClick={onClose}
             className="px-4 py-2 text-sm text-gray-600 dark:text-gray-300 hover:text-gray-800 dark:hover:text-gray-100"
@@ -126,6 +126,18 @@ export function WindowPickerModal({ isOpen, onClose, onCapture }: Props) {
     }
   };
 
+  // Pick the window on screen - the overlay hides the app and highlights windows under the mouse
+  const handlePickOnScreen = async () => {
+    try {
+      onClose();
+      // Small delay to let modal close animation complete
+      await new Promise((resolve) => setTimeout(resolve, 100));
+      await screenshotApi.showWindowPicker();
+    } catch (e) {
+      logError('WindowPickerModal:pickOnScreen', e);
+    }
+  };
+
   // Double-click to capture
   const handleDoubleClick = async (windowId: number) => {
     setSelectedId(windowId);
@@ -167,6 +179,13 @@ export function WindowPickerModal({ isOpen, onClose, onCapture }: Props) {
             Select Window to Capture
           </h2>
           <div className="flex items-center gap-2">
+            <button
+              onClick={handlePickOnScreen}
+              className="px-2 py-1 text-sm text-gray-600 dark:text-gray-300 hover:text-gray-800 dark:hover:text-gray-100"
+              aria-label="Pick window on screen"
+            >
+              ⌖ Pick on screen
+            </button>
             <button
               onClick={fetchWindows}
               disabled={loading}
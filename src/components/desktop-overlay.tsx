@@ -0,0 +1,319 @@
+// Desktop overlay component - One overlay window per monitor over a composed desktop image
+// All windows share one selection (in desktop canvas pixels) so a region can span monitors
+
+import { useState, useEffect, useCallback, useRef } from 'react';
+import { getCurrentWindow, Window } from '@tauri-apps/api/window';
+import { emit, listen } from '@tauri-apps/api/event';
+import { captureUrl, getDesktopOverlay } from '../utils/screenshot-api';
+import type { DesktopLayout, DesktopMonitor } from '../types/screenshot';
+
+interface SelectionRect {
+  startX: number;
+  startY: number;
+  endX: number;
+  endY: number;
+}
+
+// Label suffix is the id of the monitor this overlay covers
+const LABEL_PREFIX = 'desktop-overlay-';
+
+export function DesktopOverlay() {
+  const [layout, setLayout] = useState<DesktopLayout | null>(null);
+  const [monitor, setMonitor] = useState<DesktopMonitor | null>(null);
+  const [backgroundImage, setBackgroundImage] = useState<string | null>(null);
+  const [selection, setSelection] = useState<SelectionRect | null>(null);
+  const [isSelecting, setIsSelecting] = useState(false);
+  const [isActive, setIsActive] = useState(false);
+  const isClosingRef = useRef(false);
+  const containerRef = useRef<HTMLDivElement>(null);
+
+  // Load the shared desktop image and find this window's monitor
+  useEffect(() => {
+    const win = getCurrentWindow();
+    const monitorId = Number(win.label.slice(LABEL_PREFIX.length));
+
+    getDesktopOverlay().then((overlay) => {
+      const own = overlay?.layout.monitors.find(m => m.id === monitorId);
+      if (!overlay || !own) {
+        console.warn('No desktop overlay data available');
+        return;
+      }
+      setLayout(overlay.layout);
+      setMonitor(own);
+
+      const url = captureUrl(overlay.capture.id);
+      const img = new Image();
+      img.onload = async () => {
+        setBackgroundImage(url);
+        setIsActive(true);
+        await win.show();
+        await win.setFocus();
+      };
+      img.onerror = async () => {
+        console.error('Failed to load desktop screenshot');
+        setIsActive(true);
+        await win.show();
+      };
+      img.src = url;
+    }).catch((e) => {
+      console.error('Activate error:', e);
+    });
+  }, []);
+
+  // Selection updates from the other monitors' overlays
+  useEffect(() => {
+    let unlistenSelection: (() => void) | null = null;
+    let unlistenHide: (() => void) | null = null;
+
+    listen<SelectionRect | null>('desktop-selection', (event) => {
+      setSelection(event.payload);
+    }).then((fn) => {
+      unlistenSelection = fn;
+    });
+
+    listen('desktop-overlay-hide', () => {
+      isClosingRef.current = true;
+      setIsActive(false);
+      getCurrentWindow().hide();
+    }).then((fn) => {
+      unlistenHide = fn;
+    });
+
+    return () => {
+      unlistenSelection?.();
+      unlistenHide?.();
+    };
+  }, []);
+
+  // Hide every overlay, then hand the result to the main window
+  // Main closes the overlays and cuts the region from the desktop image
+  const finish = useCallback(async (region: { x: number, y: number, width: number, height: number } | null) => {
+    if (isClosingRef.current) return;
+    isClosingRef.current = true;
+
+    try {
+      await emit('desktop-overlay-hide', {});
+      const mainWindow = new Window('main');
+      if (region) {
+        await mainWindow.emit('desktop-region-selected', region);
+      } else {
+        await mainWindow.emit('desktop-selection-cancelled', {});
+      }
+    } catch (e) {
+      console.error('Emit error:', e);
+    }
+  }, []);
+
+  // Handle ESC key
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (!isActive) return;
+      if (e.key === 'Escape' || e.code === 'Escape') {
+        e.preventDefault();
+        e.stopPropagation();
+        finish(null);
+      }
+    };
+
+    window.addEventListener('keydown', handleKeyDown, true);
+    return () => window.removeEventListener('keydown', handleKeyDown, true);
+  }, [finish, isActive]);
+
+  // Focus container when active
+  useEffect(() => {
+    if (isActive && containerRef.current) {
+      containerRef.current.focus();
+    }
+  }, [isActive]);
+
+  // CSS pixels per desktop canvas pixel on this monitor
+  const scale = monitor && monitor.canvas_width > 0 ? window.innerWidth / monitor.canvas_width : 1;
+
+  // Convert a pointer position (possibly outside this window while dragging) to canvas pixels
+  const toCanvas = useCallback((clientX: number, clientY: number) => {
+    if (!monitor || !layout) return { x: 0, y: 0 };
+    const x = monitor.canvas_x + clientX / scale;
+    const y = monitor.canvas_y + clientY / scale;
+    return {
+      x: Math.round(Math.min(Math.max(x, 0), layout.canvas_width)),
+      y: Math.round(Math.min(Math.max(y, 0), layout.canvas_height)),
+    };
+  }, [monitor, layout, scale]);
+
+  const updateSelection = useCallback((next: SelectionRect | null) => {
+    setSelection(next);
+    emit('desktop-selection', next);
+  }, []);
+
+  const handlePointerDown = useCallback((e: React.PointerEvent) => {
+    if (isClosingRef.current || !isActive) return;
+    e.preventDefault();
+    // Keep receiving moves after the pointer leaves this monitor
+    e.currentTarget.setPointerCapture(e.pointerId);
+    const { x, y } = toCanvas(e.clientX, e.clientY);
+    setIsSelecting(true);
+    updateSelection({ startX: x, startY: y, endX: x, endY: y });
+  }, [isActive, toCanvas, updateSelection]);
+
+  const handlePointerMove = useCallback((e: React.PointerEvent) => {
+    if (!isSelecting || !selection || isClosingRef.current) return;
+    e.preventDefault();
+    const { x, y } = toCanvas(e.clientX, e.clientY);
+    if (x === selection.endX && y === selection.endY) return;
+    updateSelection({ ...selection, endX: x, endY: y });
+  }, [isSelecting, selection, toCanvas, updateSelection]);
+
+  const handlePointerUp = useCallback((e: React.PointerEvent) => {
+    if (!isSelecting || !selection || isClosingRef.current) return;
+    e.preventDefault();
+    setIsSelecting(false);
+
+    const region = {
+      x: Math.min(selection.startX, selection.endX),
+      y: Math.min(selection.startY, selection.endY),
+      width: Math.abs(selection.endX - selection.startX),
+      height: Math.abs(selection.endY - selection.startY),
+    };
+
+    // Minimum selection size (in CSS pixels of this monitor)
+    if (region.width * scale < 10 || region.height * scale < 10) {
+      updateSelection(null);
+      finish(null);
+      return;
+    }
+
+    finish(region);
+  }, [isSelecting, selection, scale, updateSelection, finish]);
+
+  // Selection box in this window's CSS pixels; parts on other monitors fall outside the window
+  const getSelectionStyle = (): React.CSSProperties => {
+    if (!selection || !monitor) return { display: 'none' };
+
+    const x = Math.min(selection.startX, selection.endX);
+    const y = Math.min(selection.startY, selection.endY);
+
+    return {
+      position: 'absolute',
+      left: (x - monitor.canvas_x) * scale,
+      top: (y - monitor.canvas_y) * scale,
+      width: Math.abs(selection.endX - selection.startX) * scale,
+      height: Math.abs(selection.endY - selection.startY) * scale,
+      border: '2px solid #0078d4',
+      backgroundColor: 'transparent',
+      boxShadow: '0 0 0 99999px rgba(0, 0, 0, 0.4)',
+      pointerEvents: 'none',
+      zIndex: 10,
+    };
+  };
+
+  // Dimensions label is shown on the monitor holding the selection's top-left corner
+  const labelPosition = (() => {
+    if (!selection || !monitor) return null;
+    const x = Math.min(selection.startX, selection.endX);
+    const y = Math.min(selection.startY, selection.endY);
+    const inside = x >= monitor.canvas_x && x < monitor.canvas_x + monitor.canvas_width
+      && y >= monitor.canvas_y && y < monitor.canvas_y + monitor.canvas_height;
+    if (!inside) return null;
+    return {
+      left: (x - monitor.canvas_x) * scale,
+      top: Math.max(0, (y - monitor.canvas_y) * scale - 28),
+    };
+  })();
+
+  // Always render container with black background to prevent white flash
+  return (
+    <div
+      ref={containerRef}
+      onPointerDown={isActive ? handlePointerDown : undefined}
+      onPointerMove={isActive ? handlePointerMove : undefined}
+      onPointerUp={isActive ? handlePointerUp : undefined}
+      tabIndex={0}
+      style={{
+        position: 'fixed',
+        inset: 0,
+        cursor: isActive ? 'crosshair' : 'default',
+        userSelect: 'none',
+        overflow: 'hidden',
+        outline: 'none',
+        backgroundColor: '#000',
+      }}
+    >
+      {/* This monitor's part of the desktop image */}
+      {backgroundImage && layout && monitor && (
+        <>
+          <img
+            src={backgroundImage}
+            alt=""
+            style={{
+              position: 'absolute',
+              left: -monitor.canvas_x * scale,
+              top: -monitor.canvas_y * scale,
+              width: layout.canvas_width * scale,
+              height: layout.canvas_height * scale,
+              maxWidth: 'none',
+              pointerEvents: 'none',
+            }}
+            draggable={false}
+          />
+          {/* Dim overlay - darkens the screen before selection */}
+          {!selection && (
+            <div
+              style={{
+                position: 'absolute',
+                inset: 0,
+                backgroundColor: 'rgba(0, 0, 0, 0.3)',
+                pointerEvents: 'none',
+              }}
+            />
+          )}
+        </>
+      )}
+
+      {/* Shared selection rectangle */}
+      <div style={getSelectionStyle()} />
+
+      {/* Instructions - only show when active and nothing is selected */}
+      {isActive && !selection && (
+        <div
+          style={{
+            position: 'absolute',
+            top: '50%',
+            left: '50%',
+            transform: 'translate(-50%, -50%)',
+            color: '#fff',
+            fontSize: 16,
+            backgroundColor: 'rgba(0, 0, 0, 0.7)',
+            padding: '12px 24px',
+            borderRadius: 8,
+            pointerEvents: 'none',
+            zIndex: 20,
+            textAlign: 'center',
+          }}
+        >
+          <div>Kéo để chọn vùng, có thể qua nhiều màn hình</div>
+          <div style={{ fontSize: 12, marginTop: 4, opacity: 0.8 }}>ESC để hủy</div>
+        </div>
+      )}
+
+      {/* Selection dimensions (desktop pixels) */}
+      {selection && labelPosition && (
+        <div
+          style={{
+            position: 'absolute',
+            left: labelPosition.left,
+            top: labelPosition.top,
+            color: '#fff',
+            fontSize: 12,
+            backgroundColor: 'rgba(0, 120, 212, 0.9)',
+            padding: '4px 8px',
+            borderRadius: 4,
+            pointerEvents: 'none',
+            zIndex: 20,
+          }}
+        >
+          {Math.abs(selection.endX - selection.startX)} × {Math.abs(selection.endY - selection.startY)}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 // EditorLayout - Main application layout with toolbar, canvas, and sidebar
 
 import { useCallback, useEffect, useState } from 'react';
+import { getCurrentWebview } from '@tauri-apps/api/webview';
 import { CanvasEditor } from '../canvas/canvas-editor';
 import { ZoomControls } from '../canvas/zoom-controls';
 import { Toolbar } from '../toolbar/toolbar';
@@ -11,24 +12,10 @@ import { useUIStore } from '../../stores/ui-store';
 import { useCanvasStore } from '../../stores/canvas-store';
 import { useCropStore } from '../../stores/crop-store';
 import { logError } from '../../utils/logger';
-
-// Helper: Load image and get dimensions
-function loadImageFromBytes(bytes: Uint8Array): Promise<{ width: number; height: number }> {
-  return new Promise((resolve, reject) => {
-    const blob = new Blob([bytes], { type: 'image/png' });
-    const url = URL.createObjectURL(blob);
-    const img = new Image();
-    img.onload = () => {
-      URL.revokeObjectURL(url);
-      resolve({ width: img.width, height: img.height });
-    };
-    img.onerror = () => {
-      URL.revokeObjectURL(url);
-      reject(new Error('Failed to load image'));
-    };
-    img.src = url;
-  });
-}
+import { readImageFromClipboard } from '../../utils/clipboard-api';
+import { openImageFile, showOpenImageDialog } from '../../utils/file-api';
+import { getCapturePng, releaseCapture } from '../../utils/screenshot-api';
+import type { CaptureRef } from '../../types/screenshot';
 
 export function EditorLayout() {
   const { isWindowPickerOpen, closeWindowPicker, isMonitorPickerOpen, closeMonitorPicker } = useUIStore();
@@ -46,99 +33,83 @@ export function EditorLayout() {
     [setImageFromBytes, fitToView]
   );
 
-  // Handle image load from File/Blob
-  const handleImageFile = useCallback(async (file: File | Blob) => {
+  // Show a capture imported by the backend (pasted or opened image)
+  const handleCaptureRef = useCallback(async (capture: CaptureRef) => {
     try {
-      const arrayBuffer = await file.arrayBuffer();
-      const bytes = new Uint8Array(arrayBuffer);
-      const { width, height } = await loadImageFromBytes(bytes);
+      const bytes = await getCapturePng(capture.id);
       clearCrop();
-      setImageFromBytes(bytes, width, height);
+      setImageFromBytes(bytes, capture.width, capture.height);
       setTimeout(() => fitToView(), 50);
-    } catch (e) {
-      logError('EditorLayout:handleImageFile', e);
+    } finally {
+      await releaseCapture(capture.id);
     }
   }, [clearCrop, setImageFromBytes, fitToView]);
 
-  // Handle paste from clipboard
+  // Handle paste from clipboard - the backend reads the image (or copied image file)
   useEffect(() => {
     const handlePaste = async (e: ClipboardEvent) => {
       const items = e.clipboardData?.items;
       if (!items) return;
 
-      for (const item of items) {
-        if (item.type.startsWith('image/')) {
-          e.preventDefault();
-          const file = item.getAsFile();
-          if (file) {
-            await handleImageFile(file);
-          }
-          return;
-        }
+      const hasImage = Array.from(items).some(
+        (item) => item.type.startsWith('image/') || item.kind === 'file'
+      );
+      if (!hasImage) return;
+
+      e.preventDefault();
+      try {
+        await handleCaptureRef(await readImageFromClipboard());
+      } catch (err) {
+        logError('EditorLayout:handlePaste', err);
       }
     };
 
     window.addEventListener('paste', handlePaste);
     return () => window.removeEventListener('paste', handlePaste);
-  }, [handleImageFile]);
+  }, [handleCaptureRef]);
 
-  // Handle drag-drop events (using native DOM events for Tauri compatibility)
+  // Ctrl/Cmd+O opens an image file through the backend
   useEffect(() => {
-    let dragCounter = 0;
-
-    const handleDragEnter = (e: globalThis.DragEvent) => {
+    const handleKeyDown = async (e: KeyboardEvent) => {
+      if (!(e.metaKey || e.ctrlKey) || e.shiftKey || e.altKey || e.key.toLowerCase() !== 'o') return;
       e.preventDefault();
-      e.stopPropagation();
-      dragCounter++;
-      if (e.dataTransfer?.types.includes('Files')) {
-        setIsDragging(true);
+      try {
+        const path = await showOpenImageDialog();
+        if (path) {
+          await handleCaptureRef(await openImageFile(path));
+        }
+      } catch (err) {
+        logError('EditorLayout:openImage', err);
       }
     };
 
-    const handleDragOver = (e: globalThis.DragEvent) => {
-      e.preventDefault();
-      e.stopPropagation();
-    };
-
-    const handleDragLeave = (e: globalThis.DragEvent) => {
-      e.preventDefault();
-      e.stopPropagation();
-      dragCounter--;
-      if (dragCounter === 0) {
-        setIsDragging(false);
-      }
-    };
+    window.addEventListener('keydown', handleKeyDown);
+    return () => window.removeEventListener('keydown', handleKeyDown);
+  }, [handleCaptureRef]);
 
-    const handleDrop = async (e: globalThis.DragEvent) => {
-      e.preventDefault();
-      e.stopPropagation();
-      dragCounter = 0;
-      setIsDragging(false);
-
-      const files = e.dataTransfer?.files;
-      if (files && files.length > 0) {
-        const file = files[0];
-        // Check MIME type or file extension (macOS Finder may not set MIME)
-        const isImage = file.type.startsWith('image/') ||
-          /\.(png|jpg|jpeg|gif|webp|bmp|svg|ico|tiff?)$/i.test(file.name);
-        if (isImage) {
-          await handleImageFile(file);
-        }
+  // Drag highlight follows the native drag-drop events; dropped files are opened
+  // by the backend (main window DragDrop handler), which sends them to the editor
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+
+    getCurrentWebview().onDragDropEvent((event) => {
+      switch (event.payload.type) {
+        case 'enter':
+          setIsDragging(event.payload.paths.length > 0);
+          break;
+        case 'drop':
+        case 'leave':
+          setIsDragging(false);
+          break;
       }
-    };
-
-    document.addEventListener('dragenter', handleDragEnter);
-    document.addEventListener('dragover', handleDragOver);
-    document.addEventListener('dragleave', handleDragLeave);
-    document.addEventListener('drop', handleDrop);
+    }).then((fn) => {
+      unlisten = fn;
+    });
 
     return () => {
-      document.removeEventListener('dragenter', handleDragEnter);
-      document.removeEventListener('dragover', handleDragOver);
-      document.removeEventListener('dragleave', handleDragLeave);
-      document.removeEventListener('drop', handleDrop);
+      unlisten?.();
     };
-  }, [handleImageFile]);
+  }, []);
 
   return (
     <div className="h-screen w-screen flex flex-col overflow-hidden canvas-area spatial-gap">
@@ -1,10 +1,21 @@
 // Region overlay component - Fullscreen overlay for interactive region selection
 // Shows captured screenshot as background for accurate region selection
+// In window-picking mode it highlights the window under the mouse instead,
+// using the window geometry the backend took together with the screenshot
 
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { getCurrentWindow, Window } from '@tauri-apps/api/window';
 import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
+import { captureUrl, getOverlayMonitor, getOverlayWindows } from '../utils/screenshot-api';
+import type { CaptureRef, OverlayWindowInfo, RegionSelection } from '../types/screenshot';
+
+declare global {
+  interface Window {
+    // Background capture injected by the backend when it creates this window
+    __OVERLAY_CAPTURE__?: CaptureRef | null;
+  }
+}
 
 interface SelectionRect {
   startX: number;
@@ -20,10 +31,16 @@ export function RegionOverlay() {
   const [backgroundImage, setBackgroundImage] = useState<string | null>(null);
   const [isClosing, setIsClosing] = useState(false);
   const [isActive, setIsActive] = useState(false);
+  // Pickable windows (topmost first); null in region selection mode
+  const [pickWindows, setPickWindows] = useState<OverlayWindowInfo[] | null>(null);
+  const [hoveredWindow, setHoveredWindow] = useState<OverlayWindowInfo | null>(null);
+  // Overlay monitor size in the units window geometry is reported in
+  const [monitorSize, setMonitorSize] = useState<{ width: number; height: number } | null>(null);
+  const captureIdRef = useRef<string | null>(null);
   const containerRef = useRef<HTMLDivElement>(null);
 
   // Hide overlay and emit selection result
-  const hideOverlay = useCallback(async (emitSelection: boolean, region?: { x: number, y: number, width: number, height: number }) => {
+  const hideOverlay = useCallback(async (emitSelection: boolean, region?: { x: number, y: number, width: number, height: number }, windowId?: number) => {
     if (isClosing) return;
     setIsClosing(true);
 
@@ -31,6 +48,7 @@ export function RegionOverlay() {
     setIsActive(false);
     setIsSelecting(false);
     setSelection(null);
+    setHoveredWindow(null);
 
     // Hide window FIRST
     try {
@@ -46,10 +64,19 @@ export function RegionOverlay() {
     // Emit event to main window
     try {
       const mainWindow = new Window('main');
-      if (emitSelection && region) {
-        await mainWindow.emit('region-selected', region);
+      if (emitSelection && region && captureIdRef.current) {
+        const selection: RegionSelection = { ...region, captureId: captureIdRef.current };
+        await mainWindow.emit('region-selected', selection);
+      } else if (emitSelection && windowId !== undefined) {
+        // The window is captured live, the overlay screenshot is no longer needed
+        if (captureIdRef.current) {
+          await invoke('clear_screenshot_data', { captureId: captureIdRef.current });
+        }
+        await mainWindow.emit('window-selected', { windowId });
       } else {
-        await invoke('clear_screenshot_data');
+        if (captureIdRef.current) {
+          await invoke('clear_screenshot_data', { captureId: captureIdRef.current });
+        }
         await mainWindow.emit('region-selection-cancelled', {});
       }
     } catch (e) {
@@ -61,27 +88,33 @@ export function RegionOverlay() {
   }, [isClosing]);
 
   // Activate overlay - load screenshot and show
-  const activateOverlay = useCallback(async () => {
+  const activateOverlay = useCallback(async (capture: CaptureRef | null) => {
     setIsSelecting(false);
     setSelection(null);
+    setHoveredWindow(null);
     setIsClosing(false);
 
     const win = getCurrentWindow();
 
     try {
-      // Run scaleFactor and get screenshot data in parallel
-      const [factor, screenshotData] = await Promise.all([
+      // Run scaleFactor and window geometry in parallel
+      const [factor, windows, monitor] = await Promise.all([
         win.scaleFactor(),
-        invoke<string | null>('get_screenshot_data')
+        getOverlayWindows(),
+        getOverlayMonitor(),
       ]);
 
       setScaleFactor(factor);
+      captureIdRef.current = capture?.id ?? null;
+      setPickWindows(windows);
+      setMonitorSize(monitor ? { width: monitor.width, height: monitor.height } : null);
 
-      if (screenshotData) {
-        // Preload image before showing window
+      if (capture) {
+        // Preload image (served as raw PNG bytes) before showing window
+        const url = captureUrl(capture.id);
         const img = new Image();
         img.onload = async () => {
-          setBackgroundImage(`data:image/png;base64,${screenshotData}`);
+          setBackgroundImage(url);
           setIsActive(true);
           await win.show();
           await win.setFocus();
@@ -92,7 +125,7 @@ export function RegionOverlay() {
           await win.show();
           await win.setFocus();
         };
-        img.src = `data:image/png;base64,${screenshotData}`;
+        img.src = url;
       } else {
         console.warn('No screenshot data available');
         setIsActive(true);
@@ -110,8 +143,8 @@ export function RegionOverlay() {
   useEffect(() => {
     let unlisten: (() => void) | null = null;
 
-    listen('overlay-activate', () => {
-      activateOverlay();
+    listen<CaptureRef>('overlay-activate', (event) => {
+      activateOverlay(event.payload);
     }).then((fn) => {
       unlisten = fn;
     });
@@ -121,6 +154,21 @@ export function RegionOverlay() {
     };
   }, [activateOverlay]);
 
+  // Window geometry streamed by the backend when the picker opens
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+
+    listen<OverlayWindowInfo[]>('overlay-windows', (event) => {
+      setPickWindows(event.payload);
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
   // Handle ESC key
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
@@ -141,9 +189,9 @@ export function RegionOverlay() {
     };
   }, [hideOverlay, isActive]);
 
-  // Auto-activate on mount
+  // Auto-activate on mount (the activate event may have fired before this window loaded)
   useEffect(() => {
-    activateOverlay();
+    activateOverlay(window.__OVERLAY_CAPTURE__ ?? null);
   }, [activateOverlay]);
 
   // Focus container when active
@@ -153,8 +201,23 @@ export function RegionOverlay() {
     }
   }, [isActive]);
 
+  // CSS pixels per window geometry unit (physical pixels, or points on macOS)
+  const geometryScale = monitorSize && monitorSize.width > 0
+    ? window.innerWidth / monitorSize.width
+    : 1 / scaleFactor;
+
+  // Topmost window containing a point in CSS pixels
+  const windowAt = useCallback((clientX: number, clientY: number) => {
+    if (!pickWindows) return null;
+    const x = clientX / geometryScale;
+    const y = clientY / geometryScale;
+    return pickWindows.find(w =>
+      x >= w.x && y >= w.y && x < w.x + w.width && y < w.y + w.height
+    ) ?? null;
+  }, [pickWindows, geometryScale]);
+
   const handleMouseDown = useCallback((e: React.MouseEvent) => {
-    if (isClosing || !isActive) return;
+    if (isClosing || !isActive || pickWindows) return;
     e.preventDefault();
     e.stopPropagation();
     setIsSelecting(true);
@@ -164,9 +227,14 @@ export function RegionOverlay() {
       endX: e.clientX,
       endY: e.clientY,
     });
-  }, [isClosing, isActive]);
+  }, [isClosing, isActive, pickWindows]);
 
   const handleMouseMove = useCallback((e: React.MouseEvent) => {
+    if (pickWindows && !isClosing) {
+      const hovered = windowAt(e.clientX, e.clientY);
+      setHoveredWindow(prev => prev?.id === hovered?.id ? prev : hovered);
+      return;
+    }
     if (!isSelecting || isClosing) return;
     e.preventDefault();
     setSelection(prev => prev ? {
@@ -174,9 +242,17 @@ export function RegionOverlay() {
       endX: e.clientX,
       endY: e.clientY,
     } : null);
-  }, [isSelecting, isClosing]);
+  }, [isSelecting, isClosing, pickWindows, windowAt]);
 
   const handleMouseUp = useCallback((e: React.MouseEvent) => {
+    if (pickWindows && !isClosing) {
+      e.preventDefault();
+      const picked = windowAt(e.clientX, e.clientY);
+      if (picked) {
+        hideOverlay(true, undefined, picked.id);
+      }
+      return;
+    }
     if (isClosing || !isSelecting || !selection) return;
     e.preventDefault();
 
@@ -202,7 +278,7 @@ export function RegionOverlay() {
     };
 
     hideOverlay(true, region);
-  }, [isSelecting, selection, scaleFactor, hideOverlay, isClosing]);
+  }, [isSelecting, selection, scaleFactor, hideOverlay, isClosing, pickWindows, windowAt]);
 
   // Selection box style with cutout effect
   const getSelectionStyle = (): React.CSSProperties => {
@@ -227,6 +303,29 @@ export function RegionOverlay() {
     };
   };
 
+  // Highlight box for the window under the mouse, clipped to the monitor
+  const getHighlightStyle = (): React.CSSProperties => {
+    if (!hoveredWindow) return { display: 'none' };
+
+    const left = Math.max(0, hoveredWindow.x * geometryScale);
+    const top = Math.max(0, hoveredWindow.y * geometryScale);
+    const right = Math.min(window.innerWidth, (hoveredWindow.x + hoveredWindow.width) * geometryScale);
+    const bottom = Math.min(window.innerHeight, (hoveredWindow.y + hoveredWindow.height) * geometryScale);
+
+    return {
+      position: 'absolute',
+      left,
+      top,
+      width: right - left,
+      height: bottom - top,
+      border: '2px solid #0078d4',
+      backgroundColor: 'rgba(0, 120, 212, 0.15)',
+      boxShadow: '0 0 0 9999px rgba(0, 0, 0, 0.4)',
+      pointerEvents: 'none',
+      zIndex: 10,
+    };
+  };
+
   // Always render container with black background to prevent white flash
   return (
     <div
@@ -239,7 +338,7 @@ export function RegionOverlay() {
       style={{
         position: 'fixed',
         inset: 0,
-        cursor: isActive ? 'crosshair' : 'default',
+        cursor: isActive ? (pickWindows ? 'pointer' : 'crosshair') : 'default',
         userSelect: 'none',
         overflow: 'hidden',
         outline: 'none',
@@ -264,7 +363,7 @@ export function RegionOverlay() {
             draggable={false}
           />
           {/* Dim overlay - darkens the screen before selection */}
-          {!isSelecting && (
+          {!isSelecting && !hoveredWindow && (
             <div
               style={{
                 position: 'absolute',
@@ -280,8 +379,11 @@ export function RegionOverlay() {
       {/* Selection rectangle */}
       <div style={getSelectionStyle()} />
 
+      {/* Window highlight */}
+      <div style={getHighlightStyle()} />
+
       {/* Instructions - only show when active and not selecting */}
-      {isActive && !isSelecting && (
+      {isActive && !isSelecting && !hoveredWindow && (
         <div
           style={{
             position: 'absolute',
@@ -298,11 +400,35 @@ export function RegionOverlay() {
             textAlign: 'center',
           }}
         >
-          <div>Kéo để chọn vùng</div>
+          <div>{pickWindows ? 'Nhấp để chọn cửa sổ' : 'Kéo để chọn vùng'}</div>
           <div style={{ fontSize: 12, marginTop: 4, opacity: 0.8 }}>ESC để hủy</div>
         </div>
       )}
 
+      {/* Hovered window label */}
+      {hoveredWindow && (
+        <div
+          style={{
+            position: 'absolute',
+            left: Math.max(0, hoveredWindow.x * geometryScale),
+            top: Math.max(0, hoveredWindow.y * geometryScale - 28),
+            maxWidth: 480,
+            overflow: 'hidden',
+            textOverflow: 'ellipsis',
+            whiteSpace: 'nowrap',
+            color: '#fff',
+            fontSize: 12,
+            backgroundColor: 'rgba(0, 120, 212, 0.9)',
+            padding: '4px 8px',
+            borderRadius: 4,
+            pointerEvents: 'none',
+            zIndex: 20,
+          }}
+        >
+          {hoveredWindow.app_name}{hoveredWindow.title ? ` — ${hoveredWindow.title}` : ''} · {hoveredWindow.width} × {hoveredWindow.height}
+        </div>
+      )}
+
       {/* Selection dimensions */}
       {isSelecting && selection && (
         <div
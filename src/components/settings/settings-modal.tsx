@@ -17,6 +17,7 @@ const HOTKEY_LABELS: Record<keyof HotkeyConfig, string> = {
   capture: 'Capture Screen',
   captureRegion: 'Capture Region',
   captureWindow: 'Capture Window',
+  captureActiveWindow: 'Capture Active Window',
   save: 'Quick Save',
   copy: 'Copy to Clipboard',
 };
@@ -28,7 +29,12 @@ const THEME_OPTIONS: { value: ThemeMode; label: string }[] = [
   { value: 'system', label: 'System' },
 ];
 
-const GLOBAL_HOTKEY_ACTIONS: (keyof HotkeyConfig)[] = ['capture', 'captureRegion', 'captureWindow'];
+const GLOBAL_HOTKEY_ACTIONS: (keyof HotkeyConfig)[] = [
+  'capture',
+  'captureRegion',
+  'captureWindow',
+  'captureActiveWindow',
+];
 
 function findDuplicateHotkeys(hotkeys: HotkeyConfig): Record<string, string[]> {
   const duplicates: Record<string, string[]> = {};
@@ -77,12 +83,15 @@ export function SettingsModal({ isOpen, onClose }: Props) {
         const errors = await updateShortcuts(
           settings.hotkeys.capture,
           settings.hotkeys.captureRegion,
-          settings.hotkeys.captureWindow
+          settings.hotkeys.captureWindow,
+          settings.hotkeys.captureActiveWindow
         );
 
         const errorMap: Record<string, string> = {};
         for (const err of errors) {
-          if (err.includes('Capture Region')) {
+          if (err.includes('Capture Active Window')) {
+            errorMap.captureActiveWindow = err;
+          } else if (err.includes('Capture Region')) {
             errorMap.captureRegion = err;
           } else if (err.includes('Capture Window')) {
             errorMap.captureWindow = err;
@@ -97,7 +106,13 @@ export function SettingsModal({ isOpen, onClose }: Props) {
     };
 
     checkShortcuts();
-  }, [isOpen, settings.hotkeys.capture, settings.hotkeys.captureRegion, settings.hotkeys.captureWindow]);
+  }, [
+    isOpen,
+    settings.hotkeys.capture,
+    settings.hotkeys.captureRegion,
+    settings.hotkeys.captureWindow,
+    settings.hotkeys.captureActiveWindow,
+  ]);
 
   // Handle ESC key to close modal
   useEffect(() => {
@@ -36,7 +36,7 @@ function getImageDimensions(bytes: Uint8Array): Promise<{ width: number; height:
 }
 
 export function Toolbar() {
-  const { captureFullscreen, loading, error, waylandWarning } = useScreenshot();
+  const { captureFullscreen, captureAllScreens, loading, error, waylandWarning } = useScreenshot();
   const { setImageFromBytes, clearCanvas, imageUrl, fitToView } = useCanvasStore();
   const { clearAnnotations } = useAnnotationStore();
   const { clearCrop } = useCropStore();
@@ -44,8 +44,9 @@ export function Toolbar() {
   const [showSettings, setShowSettings] = useState(false);
   const [showAbout, setShowAbout] = useState(false);
 
-  const handleCaptureFullscreen = useCallback(async () => {
-    const bytes = await captureFullscreen();
+  // Shift+click captures all monitors stitched into one image
+  const handleCaptureFullscreen = useCallback(async (e: React.MouseEvent) => {
+    const bytes = e.shiftKey ? await captureAllScreens() : await captureFullscreen();
     if (bytes) {
       try {
         const { width, height } = await getImageDimensions(bytes);
@@ -57,7 +58,7 @@ export function Toolbar() {
         logError('Toolbar:captureFullscreen', e);
       }
     }
-  }, [captureFullscreen, clearCrop, setImageFromBytes, fitToView]);
+  }, [captureFullscreen, captureAllScreens, clearCrop, setImageFromBytes, fitToView]);
 
   return (
     <div className="h-14 glass floating-panel flex items-center px-3 gap-2 overflow-visible">
@@ -68,7 +69,7 @@ export function Toolbar() {
           onClick={handleCaptureFullscreen}
           disabled={loading}
           aria-label="Capture full screen screenshot"
-          title="Capture Screen"
+          title="Capture Screen (Shift+click: all screens)"
           className="h-10 px-3 flex items-center gap-1.5 glass-btn rounded-xl text-orange-500 hover:text-orange-600 disabled:opacity-50"
         >
           <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
@@ -2,7 +2,6 @@
 
 import { useCallback } from 'react';
 import { sendNotification } from '@tauri-apps/plugin-notification';
-import { invoke } from '@tauri-apps/api/core';
 import { useExportStore } from '../stores/export-store';
 import { toast } from '../stores/toast-store';
 import { useCanvasStore } from '../stores/canvas-store';
@@ -10,11 +9,14 @@ import { useBackgroundStore } from '../stores/background-store';
 import { useSettingsStore } from '../stores/settings-store';
 import {
   stageToDataURL,
+  stageToImageData,
   dataURLToBytes,
   generateFilename,
   calculateAspectRatioExtend,
   ExportError,
+  type ExportOptions,
 } from '../utils/export-utils';
+import { copyRgbaToClipboard } from '../utils/clipboard-api';
 import {
   saveFile,
   getPicturesDir,
@@ -24,7 +26,6 @@ import {
   extractFilename,
 } from '../utils/file-api';
 import { logError } from '../utils/logger';
-import { useCropStore } from '../stores/crop-store';
 
 export function useExport() {
   const {
@@ -38,7 +39,6 @@ export function useExport() {
     startExport,
     finishExport,
   } = useExportStore();
-  const { cropRect } = useCropStore();
   const { stageRef, originalWidth, originalHeight } = useCanvasStore();
   const { getPaddingPx } = useBackgroundStore();
   const { showNotifications, saveLocation, customSavePath } = useSettingsStore();
@@ -67,12 +67,10 @@ export function useExport() {
   );
 
   /**
-   * Export stage to data URL string
+   * Stage export options for the current canvas size
    * Note: Aspect ratio is applied via canvas extension, not export-time cropping
    */
-  const exportToDataURL = useCallback(() => {
-    if (!stageRef?.current) return null;
-
+  const getStageExportOptions = useCallback((): ExportOptions => {
     // Calculate canvas dimensions (image + padding + aspect ratio extension)
     let canvasWidth: number | undefined;
     let canvasHeight: number | undefined;
@@ -88,15 +86,23 @@ export function useExport() {
       canvasHeight = aspectExtension?.height || baseHeight;
     }
 
-    return stageToDataURL(stageRef.current, {
+    return {
       format,
       quality,
       pixelRatio,
       cropRect: null,
       canvasWidth,
       canvasHeight,
-    });
-  }, [stageRef, format, quality, pixelRatio, cropRect, originalWidth, originalHeight, getPaddingPx, outputAspectRatio]);
+    };
+  }, [format, quality, pixelRatio, originalWidth, originalHeight, getPaddingPx, outputAspectRatio]);
+
+  /**
+   * Export stage to data URL string
+   */
+  const exportToDataURL = useCallback(() => {
+    if (!stageRef?.current) return null;
+    return stageToDataURL(stageRef.current, getStageExportOptions());
+  }, [stageRef, getStageExportOptions]);
 
   /**
    * Get user-friendly error message
@@ -124,25 +130,22 @@ export function useExport() {
 
   /**
    * Copy image to clipboard with loading state
-   * Uses custom Rust command with arboard for reliable clipboard access on macOS
+   * Rendered pixels go to the backend over binary IPC, which places bitmap, PNG,
+   * HTML and a file reference on the clipboard
    */
   const copyToClipboard = useCallback(async () => {
     if (isExporting) return false;
 
     startExport('clipboard');
-    const dataURL = exportToDataURL();
-    if (!dataURL) {
+    if (!stageRef?.current) {
       finishExport();
       await notify('Copy Failed', 'No image to copy. Take a screenshot first.');
       return false;
     }
 
     try {
-      // Convert data URL to base64 (remove data:image/png;base64, prefix)
-      const base64Data = dataURL.split(',')[1];
-
-      // Use custom Rust command with arboard crate for reliable clipboard
-      await invoke('copy_image_to_clipboard', { base64Data });
+      const imageData = stageToImageData(stageRef.current, getStageExportOptions());
+      await copyRgbaToClipboard(imageData.data, imageData.width, imageData.height);
 
       // Show in-app toast notification
       toast.success('Copied!', 'Image copied to clipboard');
@@ -158,7 +161,7 @@ export function useExport() {
     } finally {
       finishExport();
     }
-  }, [isExporting, exportToDataURL, startExport, finishExport, notify]);
+  }, [isExporting, stageRef, getStageExportOptions, startExport, finishExport, notify]);
 
   /**
    * Get save directory based on settings
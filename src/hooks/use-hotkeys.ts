@@ -1,6 +1,7 @@
 // useHotkeys - Listen for global hotkeys and tray events from Tauri
 
 import { useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { useCanvasStore } from '../stores/canvas-store';
@@ -8,7 +9,7 @@ import { useCropStore } from '../stores/crop-store';
 import { useUIStore } from '../stores/ui-store';
 import * as screenshotApi from '../utils/screenshot-api';
 import { logError } from '../utils/logger';
-import type { CaptureRegion } from '../types/screenshot';
+import type { CaptureRef, CaptureRegion, RegionSelection } from '../types/screenshot';
 
 // Helper: Get image dimensions from bytes
 function getImageDimensions(
@@ -157,10 +158,11 @@ export function useHotkeys(): void {
 
   // Handle region selected from overlay
   // Crops the stored screenshot to the selected region
-  const handleRegionSelected = useCallback(async (region: CaptureRegion) => {
+  const handleRegionSelected = useCallback(async (selection: RegionSelection) => {
+    const { captureId, ...region } = selection;
     try {
-      // Get stored screenshot data
-      const screenshotBase64 = await screenshotApi.getScreenshotData();
+      // Get the screenshot the region was picked on
+      const screenshotBase64 = await screenshotApi.getScreenshotData(captureId);
 
       if (screenshotBase64) {
         // Crop screenshot to selected region
@@ -173,8 +175,8 @@ export function useHotkeys(): void {
         }
       }
 
-      // Clear stored screenshot data
-      await screenshotApi.clearScreenshotData();
+      // Release stored screenshot data
+      await screenshotApi.clearScreenshotData(captureId);
     } catch (e) {
       logError('useHotkeys:regionSelected', e);
     } finally {
@@ -185,6 +187,52 @@ export function useHotkeys(): void {
     }
   }, [clearCrop, setImageFromBytes, fitToView]);
 
+  // Handle a finished timed capture or opened image (backend already showed the main window)
+  const handleDelayedCapture = useCallback(async (capture: CaptureRef) => {
+    try {
+      const bytes = await screenshotApi.getCapturePng(capture.id);
+      clearCrop();
+      setImageFromBytes(bytes, capture.width, capture.height);
+      setTimeout(() => fitToView(), 50);
+    } catch (e) {
+      logError('useHotkeys:delayedCapture', e);
+    } finally {
+      await screenshotApi.releaseCapture(capture.id);
+    }
+  }, [clearCrop, setImageFromBytes, fitToView]);
+
+  // Handle window picked on the overlay - captures it live now that the overlay is hidden
+  const handleWindowSelected = useCallback(async (windowId: number) => {
+    try {
+      const capture = await screenshotApi.captureWindowRef(windowId);
+      await handleDelayedCapture(capture);
+    } catch (e) {
+      logError('useHotkeys:windowSelected', e);
+    } finally {
+      // Show main window again
+      const appWindow = getCurrentWindow();
+      await appWindow.show();
+      appWindow.setFocus();
+    }
+  }, [handleDelayedCapture]);
+
+  // Handle region selected across monitors - cut from the composed desktop image
+  const handleDesktopRegionSelected = useCallback(async (region: CaptureRegion | null) => {
+    try {
+      const capture = await screenshotApi.finishDesktopOverlay(region);
+      if (capture) {
+        await handleDelayedCapture(capture);
+      }
+    } catch (e) {
+      logError('useHotkeys:desktopRegionSelected', e);
+    } finally {
+      // Show main window again
+      const appWindow = getCurrentWindow();
+      await appWindow.show();
+      appWindow.setFocus();
+    }
+  }, [handleDelayedCapture]);
+
   // Handle region selection cancelled
   const handleRegionCancelled = useCallback(async () => {
     // Show main window again
@@ -203,19 +251,44 @@ export function useHotkeys(): void {
     let unlistenHotkeyWindow: (() => void) | null = null;
     let unlistenRegionSelected: (() => void) | null = null;
     let unlistenRegionCancelled: (() => void) | null = null;
+    let unlistenWindowSelected: (() => void) | null = null;
+    let unlistenDesktopSelected: (() => void) | null = null;
+    let unlistenDesktopCancelled: (() => void) | null = null;
+    let unlistenDelayedComplete: (() => void) | null = null;
+    let unlistenDelayedFailed: (() => void) | null = null;
+    let unlistenImageOpened: (() => void) | null = null;
+    let unlistenImageOpenFailed: (() => void) | null = null;
+    let unlistenHotkeyCaptureComplete: (() => void) | null = null;
+    let unlistenHotkeyCaptureFailed: (() => void) | null = null;
 
     // Listen for tray capture menu events
-    listen('tray-capture-screen', () => handleCapture()).then((fn) => {
-      unlistenTrayScreen = fn;
-    });
-
-    listen('tray-capture-region', () => handleCaptureRegion()).then((fn) => {
-      unlistenTrayRegion = fn;
-    });
-
-    listen('tray-capture-window', () => handleCaptureWindow()).then((fn) => {
-      unlistenTrayWindow = fn;
-    });
+    const trayListeners = [
+      listen('tray-capture-screen', () => handleCapture()).then((fn) => {
+        unlistenTrayScreen = fn;
+      }),
+      listen('tray-capture-region', () => handleCaptureRegion()).then((fn) => {
+        unlistenTrayRegion = fn;
+      }),
+      listen('tray-capture-window', () => handleCaptureWindow()).then((fn) => {
+        unlistenTrayWindow = fn;
+      }),
+      // Image files from launch args, file association or drag-and-drop
+      listen<CaptureRef>('image-opened', (event) => {
+        handleDelayedCapture(event.payload);
+      }).then((fn) => {
+        unlistenImageOpened = fn;
+      }),
+      listen<string>('image-open-failed', (event) => {
+        logError('useHotkeys:imageOpened', event.payload);
+      }).then((fn) => {
+        unlistenImageOpenFailed = fn;
+      }),
+    ];
+
+    // Run actions from launch arguments (e.g. --capture-region) once listening
+    Promise.all(trayListeners)
+      .then(() => invoke('flush_launch_actions'))
+      .catch((e) => logError('useHotkeys:flushLaunchActions', e));
 
     // Listen for global hotkey events
     listen('hotkey-capture', () => handleCapture()).then((fn) => {
@@ -230,19 +303,66 @@ export function useHotkeys(): void {
       unlistenHotkeyWindow = fn;
     });
 
+    // Listen for captures taken in the backend by shortcuts (e.g. window matching)
+    listen<CaptureRef>('hotkey-capture-complete', (event) => {
+      handleDelayedCapture(event.payload);
+    }).then((fn) => {
+      unlistenHotkeyCaptureComplete = fn;
+    });
+
+    listen<string>('hotkey-capture-failed', (event) => {
+      logError('useHotkeys:hotkeyCapture', event.payload);
+    }).then((fn) => {
+      unlistenHotkeyCaptureFailed = fn;
+    });
+
     // Listen for region selection events from overlay window
-    listen<CaptureRegion>('region-selected', (event) => {
+    listen<RegionSelection>('region-selected', (event) => {
       handleRegionSelected(event.payload);
     }).then((fn) => {
       unlistenRegionSelected = fn;
     });
 
+    listen<{ windowId: number }>('window-selected', (event) => {
+      handleWindowSelected(event.payload.windowId);
+    }).then((fn) => {
+      unlistenWindowSelected = fn;
+    });
+
+    listen<CaptureRegion>('desktop-region-selected', (event) => {
+      handleDesktopRegionSelected(event.payload);
+    }).then((fn) => {
+      unlistenDesktopSelected = fn;
+    });
+
+    listen('desktop-selection-cancelled', () => {
+      handleDesktopRegionSelected(null);
+    }).then((fn) => {
+      unlistenDesktopCancelled = fn;
+    });
+
     listen('region-selection-cancelled', () => {
       handleRegionCancelled();
     }).then((fn) => {
       unlistenRegionCancelled = fn;
     });
 
+    // Listen for timed capture results
+    listen<CaptureRef>('delayed-capture-complete', (event) => {
+      handleDelayedCapture(event.payload);
+    }).then((fn) => {
+      unlistenDelayedComplete = fn;
+    });
+
+    listen<string>('delayed-capture-failed', (event) => {
+      logError('useHotkeys:delayedCapture', event.payload);
+      if (event.payload.includes('permission')) {
+        getCurrentWindow().emit('permission-denied', {});
+      }
+    }).then((fn) => {
+      unlistenDelayedFailed = fn;
+    });
+
     // Cleanup listeners on unmount
     return () => {
       unlistenTrayScreen?.();
@@ -253,6 +373,15 @@ export function useHotkeys(): void {
       unlistenHotkeyWindow?.();
       unlistenRegionSelected?.();
       unlistenRegionCancelled?.();
+      unlistenWindowSelected?.();
+      unlistenDesktopSelected?.();
+      unlistenDesktopCancelled?.();
+      unlistenDelayedComplete?.();
+      unlistenDelayedFailed?.();
+      unlistenImageOpened?.();
+      unlistenImageOpenFailed?.();
+      unlistenHotkeyCaptureComplete?.();
+      unlistenHotkeyCaptureFailed?.();
     };
-  }, [handleCapture, handleCaptureRegion, handleCaptureWindow, handleRegionSelected, handleRegionCancelled]);
+  }, [handleCapture, handleCaptureRegion, handleCaptureWindow, handleRegionSelected, handleRegionCancelled, handleDelayedCapture, handleWindowSelected, handleDesktopRegionSelected]);
 }
@@ -19,6 +19,7 @@ interface UseScreenshotReturn {
 
   // Capture actions - return raw bytes
   captureFullscreen: () => Promise<Uint8Array | null>;
+  captureAllScreens: () => Promise<Uint8Array | null>;
   captureRegion: (region: CaptureRegion) => Promise<Uint8Array | null>;
   captureRegionInteractive: () => Promise<Uint8Array | null>;
   captureWindow: (windowId: number) => Promise<Uint8Array | null>;
@@ -67,6 +68,21 @@ export function useScreenshot(): UseScreenshotReturn {
     }
   }, []);
 
+  const captureAllScreens = useCallback(async (): Promise<Uint8Array | null> => {
+    setLoading(true);
+    setError(null);
+    try {
+      // Every monitor stitched by desktop layout, app window hidden
+      const bytes = await api.captureAllScreensHidden();
+      return bytes;
+    } catch (e) {
+      setError(String(e));
+      return null;
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
   const captureRegion = useCallback(
     async (region: CaptureRegion): Promise<Uint8Array | null> => {
       setLoading(true);
@@ -135,6 +151,7 @@ export function useScreenshot(): UseScreenshotReturn {
     loading,
     error,
     captureFullscreen,
+    captureAllScreens,
     captureRegion,
     captureRegionInteractive,
     captureWindow,
@@ -21,14 +21,15 @@ export function useSyncShortcuts(): UseSyncShortcutsReturn {
       const errors = await updateShortcuts(
         hotkeys.capture,
         hotkeys.captureRegion,
-        hotkeys.captureWindow
+        hotkeys.captureWindow,
+        hotkeys.captureActiveWindow
       );
       setSyncErrors(errors);
     } catch (e) {
       console.error('Failed to sync shortcuts:', e);
       setSyncErrors([String(e)]);
     }
-  }, [hotkeys.capture, hotkeys.captureRegion, hotkeys.captureWindow]);
+  }, [hotkeys.capture, hotkeys.captureRegion, hotkeys.captureWindow, hotkeys.captureActiveWindow]);
 
   useEffect(() => {
     syncShortcuts();
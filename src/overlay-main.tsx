@@ -1,12 +1,17 @@
 // Overlay entry point - React entry for region selection overlay window
+// Per-monitor desktop overlays (label "desktop-overlay-<id>") share this entry
 
 import React from 'react';
 import ReactDOM from 'react-dom/client';
+import { getCurrentWindow } from '@tauri-apps/api/window';
 import { RegionOverlay } from './components/region-overlay';
+import { DesktopOverlay } from './components/desktop-overlay';
 import './styles.css';
 
+const isDesktopOverlay = getCurrentWindow().label.startsWith('desktop-overlay-');
+
 ReactDOM.createRoot(document.getElementById('root')!).render(
   <React.StrictMode>
-    <RegionOverlay />
+    {isDesktopOverlay ? <DesktopOverlay /> : <RegionOverlay />}
   </React.StrictMode>
 );
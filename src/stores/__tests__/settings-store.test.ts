@@ -9,6 +9,7 @@ describe('Settings Store', () => {
         capture: 'CommandOrControl+Shift+C',
         captureRegion: 'CommandOrControl+Shift+R',
         captureWindow: 'CommandOrControl+Shift+W',
+        captureActiveWindow: 'CommandOrControl+Shift+A',
         save: 'CommandOrControl+S',
         copy: 'CommandOrControl+Shift+V',
       },
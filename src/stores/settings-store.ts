@@ -46,6 +46,7 @@ export interface HotkeyConfig {
   capture: string;
   captureRegion: string;
   captureWindow: string;
+  captureActiveWindow: string;
   save: string;
   copy: string;
 }
@@ -84,6 +85,7 @@ const DEFAULT_HOTKEYS: HotkeyConfig = {
   capture: 'CommandOrControl+Option+1',
   captureRegion: 'CommandOrControl+Option+2',
   captureWindow: 'CommandOrControl+Option+3',
+  captureActiveWindow: 'CommandOrControl+Option+4',
   save: 'CommandOrControl+S',
   copy: 'CommandOrControl+C',
 };
@@ -109,11 +111,12 @@ export const useSettingsStore = create<SettingsState>()(
           set((state) => {
             const newHotkeys = { ...state.hotkeys, [action]: shortcut };
             // Update global shortcuts in backend for capture-related hotkeys
-            if (['capture', 'captureRegion', 'captureWindow'].includes(action)) {
+            if (['capture', 'captureRegion', 'captureWindow', 'captureActiveWindow'].includes(action)) {
               updateShortcuts(
                 newHotkeys.capture,
                 newHotkeys.captureRegion,
-                newHotkeys.captureWindow
+                newHotkeys.captureWindow,
+                newHotkeys.captureActiveWindow
               ).catch(console.error);
             }
             return { hotkeys: newHotkeys };
@@ -132,6 +135,15 @@ export const useSettingsStore = create<SettingsState>()(
     }),
     {
       name: 'beautyshot-settings',
+      // Hotkeys added in later versions get their defaults instead of undefined
+      merge: (persisted, current) => {
+        const saved = persisted as Partial<SettingsState> | undefined;
+        return {
+          ...current,
+          ...saved,
+          hotkeys: { ...current.hotkeys, ...saved?.hotkeys },
+        };
+      },
     }
   )
 );
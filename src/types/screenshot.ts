@@ -8,6 +8,8 @@ export interface MonitorInfo {
   x: number;
   y: number;
   is_primary: boolean;
+  /** Display scale (2 on Retina/200%); width/height are physical pixels */
+  scale_factor: number;
 }
 
 export interface WindowInfo {
@@ -18,6 +20,97 @@ export interface WindowInfo {
   y: number;
   width: number;
   height: number;
+  pid: number;
+  is_focused: boolean;
+  is_minimized: boolean;
+}
+
+// Window lookup for findWindows / captureWindowMatching; omitted fields match any window
+export interface WindowQuery {
+  title?: string;
+  app_name?: string;
+  // Treat title/app_name as case-insensitive regular expressions instead of substrings
+  regex?: boolean;
+  pid?: number;
+  focused?: boolean;
+}
+
+// How much of a window a capture includes
+// native: whatever the platform returns; content: client area only;
+// frame: with title bar and borders; shadow: frame plus a rendered drop shadow
+export type WindowFraming = 'native' | 'content' | 'frame' | 'shadow';
+
+export interface WindowCaptureOptions {
+  framing?: WindowFraming;
+  // Keep the area around the window transparent (default) instead of flattening onto white
+  transparent?: boolean;
+}
+
+// Pickable window on the overlay monitor, offset from the monitor origin
+// in the overlay monitor's units; z is the stacking order (0 = topmost)
+export interface OverlayWindowInfo {
+  id: number;
+  app_name: string;
+  title: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  z: number;
+}
+
+// What fills desktop areas no monitor covers in an all-screens capture
+// desktop: the system desktop background color (black when unknown)
+export type GapFill = 'transparent' | 'black' | 'desktop';
+
+// Pixel density mixed-DPI monitors are resampled to: the densest or least dense monitor
+export type DpiScale = 'highest' | 'lowest';
+
+export interface DesktopCaptureOptions {
+  gap_fill?: GapFill;
+  dpi?: DpiScale;
+}
+
+// Outcome of capturing one monitor with captureEachMonitor
+// capture is set when kept in the store, path when saved to a folder, error when it failed
+export interface MonitorCaptureResult {
+  monitor_id: number;
+  monitor_name: string;
+  capture: CaptureRef | null;
+  path: string | null;
+  error: string | null;
+}
+
+// A monitor's place on the desktop (x/y/width/height, MonitorInfo units)
+// and on the composed desktop canvas (canvas_*, canvas pixels)
+export interface DesktopMonitor {
+  id: number;
+  name: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  canvas_x: number;
+  canvas_y: number;
+  canvas_width: number;
+  canvas_height: number;
+}
+
+// How desktop coordinates map onto the composed canvas; scale is canvas pixels per desktop unit
+export interface DesktopLayout {
+  x: number;
+  y: number;
+  scale: number;
+  canvas_width: number;
+  canvas_height: number;
+  monitors: DesktopMonitor[];
+}
+
+// Composed desktop image shared by the per-monitor desktop overlays
+export interface DesktopOverlayData {
+  capture: CaptureRef;
+  backend: string;
+  layout: DesktopLayout;
 }
 
 export interface CaptureRegion {
@@ -26,3 +119,28 @@ export interface CaptureRegion {
   width: number;
   height: number;
 }
+
+// Region picked on the overlay, with the id of the overlay background it was picked on
+export interface RegionSelection extends CaptureRegion {
+  captureId: string;
+}
+
+// Reference to a capture held in the backend capture store
+export interface CaptureRef {
+  id: string;
+  width: number;
+  height: number;
+}
+
+// What a timed capture grabs when its countdown ends
+export type DelayedCaptureMode =
+  | { type: 'fullscreen' }
+  | { type: 'region' }
+  | { type: 'monitor'; monitor_id: number }
+  | { type: 'window'; window_id: number };
+
+// Countdown tick emitted once per second during a timed capture
+export interface CountdownTick {
+  remaining: number;
+  total: number;
+}
@@ -3,6 +3,7 @@ import {
   generateFilename,
   stageToDataURL,
   stageToBlob,
+  stageToImageData,
   dataURLToBytes,
   calculateAspectRatioExtend,
   ExportError,
@@ -26,6 +27,7 @@ interface MockStageConfig {
 interface MockStage {
   toDataURL: ReturnType<typeof vi.fn>;
   toBlob: ReturnType<typeof vi.fn>;
+  toCanvas: ReturnType<typeof vi.fn>;
   x: ReturnType<typeof vi.fn>;
   y: ReturnType<typeof vi.fn>;
   scaleX: ReturnType<typeof vi.fn>;
@@ -55,12 +57,23 @@ const createMockStage = (): Konva.Stage & MockStage => {
         config.callback(blob);
       }
     }),
+    toCanvas: vi.fn(() => ({
+      width: 2,
+      height: 1,
+      getContext: () => ({
+        getImageData: vi.fn((_x: number, _y: number, width: number, height: number) => ({
+          data: new Uint8ClampedArray(width * height * 4).fill(255),
+          width,
+          height,
+        })),
+      }),
+    })),
   };
   return mockStage as Konva.Stage & MockStage;
 };
 
 // Helper to get mock call config - accepts a mock with .mock.calls
-const getMockCallConfig = (mockStage: MockStage, method: 'toDataURL' | 'toBlob'): MockStageConfig => {
+const getMockCallConfig = (mockStage: MockStage, method: 'toDataURL' | 'toBlob' | 'toCanvas'): MockStageConfig => {
   return mockStage[method].mock.calls[0]?.[0] as MockStageConfig;
 };
 
@@ -208,6 +221,46 @@ describe('Export Utils', () => {
     });
   });
 
+  describe('stageToImageData', () => {
+    let mockStage: Konva.Stage;
+
+    beforeEach(() => {
+      mockStage = createMockStage();
+    });
+
+    it('should return RGBA pixels of the rendered canvas', () => {
+      const imageData = stageToImageData(mockStage, { format: 'png', quality: 1, pixelRatio: 1 });
+
+      expect(imageData.width).toBe(2);
+      expect(imageData.height).toBe(1);
+      expect(imageData.data).toHaveLength(8);
+    });
+
+    it('should export the canvas area at the given pixel ratio', () => {
+      stageToImageData(mockStage, {
+        format: 'png',
+        quality: 1,
+        pixelRatio: 2,
+        canvasWidth: 800,
+        canvasHeight: 600,
+      });
+
+      const callConfig = getMockCallConfig(mockStage as unknown as MockStage, 'toCanvas');
+      expect(callConfig.pixelRatio).toBe(2);
+      expect(callConfig.x).toBe(0);
+      expect(callConfig.width).toBe(800);
+      expect(callConfig.height).toBe(600);
+    });
+
+    it('should restore the stage transform after export', () => {
+      stageToImageData(mockStage, { format: 'png', quality: 1, pixelRatio: 1 });
+
+      const mock = mockStage as unknown as MockStage;
+      expect(mock.position).toHaveBeenCalledTimes(2);
+      expect(mock.scale).toHaveBeenCalledTimes(2);
+    });
+  });
+
   describe('stageToBlob', () => {
     let mockStage: Konva.Stage;
 
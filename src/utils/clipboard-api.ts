@@ -0,0 +1,39 @@
+// Rich clipboard API - Tauri IPC wrapper
+// Copies put a bitmap, image/png, an HTML <img> data URI and a file reference
+// (text/uri-list) on the clipboard at once, so paste works in chat apps,
+// file managers and rich-text editors
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CaptureRef } from "../types/screenshot";
+import { invokeWithRgba } from "./screenshot-api";
+
+/**
+ * Copy a stored capture in every clipboard format
+ * @param filePath - Saved file to reference; a temporary PNG is used when omitted
+ */
+export async function copyCaptureToClipboard(
+  captureId: string,
+  filePath?: string
+): Promise<void> {
+  await invoke("copy_capture_to_clipboard", { captureId, filePath });
+}
+
+/**
+ * Copy raw RGBA pixels (e.g. from canvas getImageData) in every clipboard format
+ * @param filePath - Saved file to reference; a temporary PNG is used when omitted
+ */
+export async function copyRgbaToClipboard(
+  rgba: Uint8Array | Uint8ClampedArray,
+  width: number,
+  height: number,
+  filePath?: string
+): Promise<void> {
+  await invokeWithRgba<void>("copy_rgba_to_clipboard", rgba, width, height, { filePath });
+}
+
+/**
+ * Import the image on the clipboard (or the first copied image file) as a capture
+ */
+export async function readImageFromClipboard(): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("read_image_from_clipboard");
+}
@@ -0,0 +1,102 @@
+// Native export API - Tauri IPC wrapper
+// Encoding happens in Rust so formats and quality settings don't depend on the canvas
+
+import { invoke } from "@tauri-apps/api/core";
+import { invokeWithRgba } from "./screenshot-api";
+
+export type PngFilter = "none" | "sub" | "up" | "avg" | "paeth" | "adaptive";
+
+export type ExportSpec =
+  | { format: "png"; compression?: number; filter?: PngFilter; optimize?: boolean }
+  | { format: "jpeg"; quality?: number }
+  | { format: "webp" }
+  | { format: "avif"; quality?: number; speed?: number }
+  | { format: "tiff" }
+  | { format: "bmp" }
+  | { format: "ico" };
+
+export interface OptimizeReport {
+  original_size: number;
+  optimized_size: number;
+  bytes_saved: number;
+  encoding: string;
+}
+
+/** Provenance embedded in (or read back from) PNG, JPEG and WebP files */
+export interface ImageProvenance {
+  captured_at: string | null;
+  app_name: string | null;
+  window_title: string | null;
+  monitor_name: string | null;
+  scale_factor: number | null;
+  app_version: string | null;
+  description: string | null;
+}
+
+/** Request to embed provenance; other formats are written without it */
+export interface EmbedOptions {
+  description?: string;
+}
+
+/** Format-independent export settings */
+export interface ExportOptions {
+  /** Capture the pixels came from (exportImage only); supplies scale factor and provenance */
+  capture_id?: string;
+  /** Display scale of the pixels, written as PNG pHYs / JPEG density; defaults to the capture's */
+  scale_factor?: number;
+  /** Downscale HiDPI pixels to logical (1x) size */
+  logical_size?: boolean;
+  /** Embed capture time, source app/window, monitor and scale factor */
+  embed?: EmbedOptions;
+}
+
+export interface ExportResult {
+  path: string;
+  byte_size: number;
+  optimization: OptimizeReport | null;
+}
+
+/**
+ * Encode a stored capture and write it to disk
+ */
+export async function exportCapture(
+  captureId: string,
+  spec: ExportSpec,
+  path: string,
+  options?: ExportOptions
+): Promise<ExportResult> {
+  return await invoke<ExportResult>("export_capture", { captureId, spec, path, options });
+}
+
+/**
+ * Encode raw RGBA pixels (e.g. from canvas getImageData) and write them to disk
+ */
+export async function exportImage(
+  rgba: Uint8Array | Uint8ClampedArray,
+  width: number,
+  height: number,
+  spec: ExportSpec,
+  path: string,
+  options?: ExportOptions
+): Promise<ExportResult> {
+  return await invokeWithRgba<ExportResult>("export_image", rgba, width, height, {
+    spec,
+    path,
+    options,
+  });
+}
+
+/**
+ * Losslessly shrink a saved PNG in place (palette/alpha reduction, max deflate,
+ * metadata stripped); the file is only rewritten when it gets smaller
+ */
+export async function optimizePngFile(path: string): Promise<OptimizeReport> {
+  return await invoke<OptimizeReport>("optimize_png_file", { path });
+}
+
+/**
+ * Read embedded provenance back from a saved image (fields are null when absent)
+ */
+export async function readImageMetadata(path: string): Promise<ImageProvenance> {
+  return await invoke<ImageProvenance>("read_image_metadata", { path });
+}
@@ -126,6 +126,51 @@ export function stageToDataURL(
   return dataURL;
 }
 
+/**
+ * Render Konva stage to raw RGBA pixels (for binary IPC, e.g. the clipboard)
+ * Uses the same area and pixel ratio as stageToDataURL; format/quality are ignored
+ */
+export function stageToImageData(
+  stage: Konva.Stage,
+  options: ExportOptions
+): ImageData {
+  const { pixelRatio, cropRect, canvasWidth, canvasHeight } = options;
+
+  // Save current transform state
+  const savedPosition = { x: stage.x(), y: stage.y() };
+  const savedScale = { x: stage.scaleX(), y: stage.scaleY() };
+
+  // Reset transform for accurate export (content at origin, no scaling)
+  stage.position({ x: 0, y: 0 });
+  stage.scale({ x: 1, y: 1 });
+
+  const exportConfig: Parameters<typeof stage.toCanvas>[0] = { pixelRatio };
+
+  if (cropRect) {
+    exportConfig.x = cropRect.x;
+    exportConfig.y = cropRect.y;
+    exportConfig.width = cropRect.width;
+    exportConfig.height = cropRect.height;
+  } else if (canvasWidth && canvasHeight) {
+    exportConfig.x = 0;
+    exportConfig.y = 0;
+    exportConfig.width = canvasWidth;
+    exportConfig.height = canvasHeight;
+  }
+
+  const canvas = stage.toCanvas(exportConfig);
+
+  // Restore transform state
+  stage.position(savedPosition);
+  stage.scale(savedScale);
+
+  const context = canvas.getContext('2d');
+  if (!context) {
+    throw new ExportError('Failed to read canvas pixels', 'DECODE_ERROR');
+  }
+  return context.getImageData(0, 0, canvas.width, canvas.height);
+}
+
 /**
  * Export Konva stage to Blob (async)
  * Temporarily resets stage transform to export content at original position/scale
@@ -1,8 +1,9 @@
 // File API - TypeScript wrappers for Tauri file operations
 
 import { invoke } from '@tauri-apps/api/core';
-import { save } from '@tauri-apps/plugin-dialog';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import type { ExportFormat } from '../stores/export-store';
+import type { CaptureRef } from '../types/screenshot';
 
 /**
  * Normalize Windows extended-length path prefix (\\?\)
@@ -73,3 +74,23 @@ export async function showSaveDialog(
 
   return path;
 }
+
+/**
+ * Show native open dialog for an image file
+ */
+export async function showOpenImageDialog(): Promise<string | null> {
+  return await open({
+    multiple: false,
+    directory: false,
+    filters: [{ name: 'Images', extensions: ['png', 'jpg', 'jpeg', 'webp', 'bmp', 'gif'] }],
+  });
+}
+
+/**
+ * Open an existing image (PNG, JPEG, WebP, BMP or GIF) into the backend capture store
+ * @param path - Image file to import
+ * @returns Reference to the stored capture
+ */
+export async function openImageFile(path: string): Promise<CaptureRef> {
+  return await invoke<CaptureRef>('open_image_file', { path });
+}
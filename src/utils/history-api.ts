@@ -0,0 +1,108 @@
+// Capture history API - Tauri IPC wrapper
+// Persistent on-disk library of past captures managed by the Rust backend
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CaptureRef } from "../types/screenshot";
+
+export interface CaptureMetadata {
+  kind: "monitor" | "region" | "window" | "thumbnail" | "imported";
+  monitor_id: number | null;
+  monitor_name: string | null;
+  window_id: number | null;
+  app_name: string | null;
+  window_title: string | null;
+  scale_factor: number;
+  captured_at: number;
+  backend: string;
+  cursor_x: number | null;
+  cursor_y: number | null;
+}
+
+export interface HistoryEntry {
+  id: string;
+  created_at: number;
+  width: number;
+  height: number;
+  byte_size: number;
+  pinned: boolean;
+  metadata: CaptureMetadata;
+}
+
+export interface HistoryQuery {
+  from?: number;
+  to?: number;
+  app_name?: string;
+  window_title?: string;
+  pinned_only?: boolean;
+  limit?: number;
+}
+
+export interface HistorySettings {
+  auto_record: boolean;
+  retention: {
+    max_count: number | null;
+    max_bytes: number | null;
+    max_age_days: number | null;
+  };
+}
+
+/**
+ * Save a stored capture into the history library
+ * @param captureId - Capture ID from a CaptureRef
+ */
+export async function addToHistory(captureId: string): Promise<HistoryEntry> {
+  return await invoke<HistoryEntry>("add_to_history", { captureId });
+}
+
+/**
+ * List history entries, newest first
+ * @param query - Optional date/app/title filters
+ */
+export async function listHistory(query?: HistoryQuery): Promise<HistoryEntry[]> {
+  return await invoke<HistoryEntry[]>("list_history", { query: query ?? null });
+}
+
+/**
+ * Get the PNG thumbnail of a history entry
+ * @returns PNG image bytes as Uint8Array
+ */
+export async function getHistoryThumbnail(id: string): Promise<Uint8Array> {
+  const buffer = await invoke<ArrayBuffer>("get_history_thumbnail", { id });
+  return new Uint8Array(buffer);
+}
+
+/**
+ * Load a history entry back into the capture store
+ * @returns Reference to the reopened capture
+ */
+export async function reopenHistoryEntry(id: string): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("reopen_history_entry", { id });
+}
+
+/**
+ * Pin or unpin a history entry (pinned entries are never pruned)
+ */
+export async function setHistoryPinned(id: string, pinned: boolean): Promise<HistoryEntry> {
+  return await invoke<HistoryEntry>("set_history_pinned", { id, pinned });
+}
+
+/**
+ * Delete a history entry and its files
+ */
+export async function deleteHistoryEntry(id: string): Promise<void> {
+  await invoke("delete_history_entry", { id });
+}
+
+/**
+ * Get history settings (auto-record and retention policy)
+ */
+export async function getHistorySettings(): Promise<HistorySettings> {
+  return await invoke<HistorySettings>("get_history_settings");
+}
+
+/**
+ * Update history settings; entries beyond the new retention policy are pruned
+ */
+export async function updateHistorySettings(settings: HistorySettings): Promise<void> {
+  await invoke("update_history_settings", { settings });
+}
@@ -0,0 +1,60 @@
+// Interval / burst capture API - Tauri IPC wrapper
+// Sessions run on a backend thread and report through events:
+// "interval-capture-progress" (IntervalCaptureProgress) and
+// "interval-capture-finished" (IntervalCaptureSummary)
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface FrameRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface IntervalCaptureOptions {
+  monitor_id?: number;
+  region?: FrameRegion;
+  interval_ms: number;
+  max_frames?: number;
+  duration_secs?: number;
+  output_dir?: string;
+}
+
+export interface IntervalCaptureProgress {
+  frame: number;
+  total: number;
+  elapsed_ms: number;
+  path: string | null;
+  error: string | null;
+}
+
+export interface IntervalCaptureSummary {
+  folder: string;
+  frames_saved: number;
+  stopped: boolean;
+  error: string | null;
+}
+
+/**
+ * Start an interval or burst capture session
+ * @returns Folder the frames are written to
+ */
+export async function startIntervalCapture(options: IntervalCaptureOptions): Promise<string> {
+  return await invoke<string>("start_interval_capture", { options });
+}
+
+/**
+ * Stop the running session
+ * @returns true if a session was running
+ */
+export async function stopIntervalCapture(): Promise<boolean> {
+  return await invoke<boolean>("stop_interval_capture");
+}
+
+/**
+ * Check whether a session is running
+ */
+export async function isIntervalCaptureRunning(): Promise<boolean> {
+  return await invoke<boolean>("is_interval_capture_running");
+}
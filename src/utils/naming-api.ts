@@ -0,0 +1,62 @@
+// Filename templating API - Tauri IPC wrapper
+// Templates use {date[:fmt]}, {time[:fmt]}, {app}, {title}, {monitor}, {kind},
+// {width}, {height}, {counter[:digits]} and {ext}; "/" creates subfolders
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ExportResult, ExportSpec } from "./export-api";
+
+export type CollisionPolicy = "increment" | "overwrite" | "ask";
+
+export interface NamingSettings {
+  base_dir: string | null;
+  template: string;
+  collision: CollisionPolicy;
+  auto_save: boolean;
+  auto_save_format: ExportSpec;
+  embed_metadata: boolean;
+  counter: number;
+}
+
+export interface ResolvedPath {
+  path: string;
+  conflict: boolean;
+}
+
+export interface NamedSave {
+  result: ExportResult | null;
+  conflict: string | null;
+}
+
+/**
+ * Get naming and auto-save settings
+ */
+export async function getNamingSettings(): Promise<NamingSettings> {
+  return await invoke<NamingSettings>("get_naming_settings");
+}
+
+/**
+ * Update naming and auto-save settings; rejects invalid templates
+ */
+export async function updateNamingSettings(settings: NamingSettings): Promise<void> {
+  await invoke("update_naming_settings", { settings });
+}
+
+/**
+ * Preview the path a capture would be saved to
+ */
+export async function previewCapturePath(captureId: string, spec: ExportSpec): Promise<ResolvedPath> {
+  return await invoke<ResolvedPath>("preview_capture_path", { captureId, spec });
+}
+
+/**
+ * Save a capture under its templated name
+ * With the "ask" policy an existing file yields `conflict`; confirm by calling
+ * again with overwrite = true
+ */
+export async function saveCaptureNamed(
+  captureId: string,
+  spec: ExportSpec,
+  overwrite = false
+): Promise<NamedSave> {
+  return await invoke<NamedSave>("save_capture_named", { captureId, spec, overwrite });
+}
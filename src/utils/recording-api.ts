@@ -0,0 +1,58 @@
+// Region recording API - Tauri IPC wrapper
+// Records a monitor region to animated GIF or APNG on a backend thread and reports
+// through events: "recording-progress" (RecordingProgress),
+// "recording-finished" (RecordingResult) and "recording-failed" (error string)
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type RecordingFormat = "gif" | "apng";
+
+export interface RecordingOptions {
+  monitor_id: number;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  format: RecordingFormat;
+  fps?: number;
+  max_duration_secs?: number;
+  max_colors?: number;
+  quantize_speed?: number;
+  output_path?: string;
+}
+
+export interface RecordingProgress {
+  captured: number;
+  unique: number;
+  elapsed_ms: number;
+}
+
+export interface RecordingResult {
+  path: string;
+  format: RecordingFormat;
+  frames: number;
+  duration_ms: number;
+  byte_size: number;
+}
+
+/**
+ * Start recording a region (coordinates relative to the monitor)
+ */
+export async function startRecording(options: RecordingOptions): Promise<void> {
+  await invoke("start_recording", { options });
+}
+
+/**
+ * Stop the running recording; the file is saved in the background
+ * @returns true if a recording was running
+ */
+export async function stopRecording(): Promise<boolean> {
+  return await invoke<boolean>("stop_recording");
+}
+
+/**
+ * Check whether a recording is in progress
+ */
+export async function isRecording(): Promise<boolean> {
+  return await invoke<boolean>("is_recording");
+}
@@ -3,7 +3,21 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import type { MonitorInfo, WindowInfo } from "../types/screenshot";
+import type { ExportSpec } from "./export-api";
+import type {
+  CaptureRef,
+  CaptureRegion,
+  DelayedCaptureMode,
+  DesktopCaptureOptions,
+  DesktopLayout,
+  DesktopOverlayData,
+  MonitorCaptureResult,
+  MonitorInfo,
+  OverlayWindowInfo,
+  WindowCaptureOptions,
+  WindowInfo,
+  WindowQuery,
+} from "../types/screenshot";
 
 // Delay for window hide - allows OS to process hide before capture
 const MACOS_HIDE_DELAY_MS = 10;
@@ -51,10 +65,14 @@ export async function captureRegion(x: number, y: number, width: number, height:
 /**
  * Capture a specific window by ID
  * @param windowId - The window ID to capture
+ * @param options - Framing (content, frame, shadow) and background; platform-native when omitted
  * @returns PNG image bytes as Uint8Array
  */
-export async function captureWindow(windowId: number): Promise<Uint8Array> {
-  const base64 = await invoke<string>("capture_window", { windowId });
+export async function captureWindow(
+  windowId: number,
+  options?: WindowCaptureOptions
+): Promise<Uint8Array> {
+  const base64 = await invoke<string>("capture_window", { windowId, options });
   return base64ToBytes(base64);
 }
 
@@ -128,6 +146,166 @@ export async function captureRegionFromMonitor(
   return base64ToBytes(base64);
 }
 
+/**
+ * Build the URL that loads a stored capture directly as PNG (no base64 round-trip)
+ * @param id - Capture ID from a CaptureRef
+ * @returns URL usable as an <img> src or with fetch()
+ */
+export function captureUrl(id: string): string {
+  return isWindows
+    ? `http://capture.localhost/${encodeURIComponent(id)}`
+    : `capture://localhost/${encodeURIComponent(id)}`;
+}
+
+/**
+ * Capture the primary monitor into the backend capture store
+ * @returns Reference to the stored capture
+ */
+export async function captureFullscreenRef(): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_fullscreen_ref");
+}
+
+/**
+ * Capture a specific monitor into the backend capture store
+ * @param monitorId - The monitor ID to capture
+ * @returns Reference to the stored capture
+ */
+export async function captureMonitorRef(monitorId: number): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_monitor_ref", { monitorId });
+}
+
+/**
+ * Capture a region of a specific monitor into the backend capture store
+ * @returns Reference to the stored capture
+ */
+export async function captureRegionFromMonitorRef(
+  x: number,
+  y: number,
+  width: number,
+  height: number,
+  monitorId: number
+): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_region_from_monitor_ref", {
+    x,
+    y,
+    width,
+    height,
+    monitorId,
+  });
+}
+
+/**
+ * Capture a window thumbnail into the backend capture store
+ * @returns Reference to the stored thumbnail
+ */
+export async function getWindowThumbnailRef(windowId: number, maxSize: number = 200): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("get_window_thumbnail_ref", { windowId, maxSize });
+}
+
+/**
+ * Capture a specific window into the backend capture store
+ * @param windowId - The window ID to capture
+ * @param options - Framing (content, frame, shadow) and background; platform-native when omitted
+ * @returns Reference to the stored capture
+ */
+export async function captureWindowRef(
+  windowId: number,
+  options?: WindowCaptureOptions
+): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_window_ref", { windowId, options });
+}
+
+/**
+ * Find windows by title/app pattern, process or focus, best match first
+ * @param query - Match criteria; omitted fields match any window
+ */
+export async function findWindows(query: WindowQuery): Promise<WindowInfo[]> {
+  return await invoke<WindowInfo[]>("find_windows", { query });
+}
+
+/**
+ * Capture the best window match for a query into the backend capture store
+ * @param query - Match criteria; omitted fields match any window
+ * @param options - Framing (content, frame, shadow) and background
+ * @returns Reference to the stored capture
+ */
+export async function captureWindowMatching(
+  query: WindowQuery,
+  options?: WindowCaptureOptions
+): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_window_matching", { query, options });
+}
+
+/**
+ * Capture the focused window (of another app) into the backend capture store
+ * @param options - Framing (content, frame, shadow) and background
+ * @returns Reference to the stored capture
+ */
+export async function captureActiveWindow(options?: WindowCaptureOptions): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_active_window", { options });
+}
+
+/**
+ * Capture the topmost window under the mouse pointer into the backend capture store
+ * @param options - Framing (content, frame, shadow) and background
+ * @returns Reference to the stored capture
+ */
+export async function captureWindowUnderCursor(options?: WindowCaptureOptions): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_window_under_cursor", { options });
+}
+
+/**
+ * Start a backend-driven timed capture; the main window is hidden during the countdown
+ * Results arrive as delayed-capture-complete / delayed-capture-failed events
+ * @param mode - What to capture when the countdown ends
+ * @param seconds - Delay in seconds (1-60)
+ */
+export async function captureAfterDelay(mode: DelayedCaptureMode, seconds: number): Promise<void> {
+  await invoke("capture_after_delay", { mode, seconds });
+}
+
+/**
+ * Cancel a pending timed capture
+ * @returns true if a countdown was running
+ */
+export async function cancelDelayedCapture(): Promise<boolean> {
+  return await invoke<boolean>("cancel_delayed_capture");
+}
+
+/**
+ * Fetch a stored capture as PNG bytes over binary IPC
+ * @param id - Capture ID from a CaptureRef
+ * @returns PNG image bytes as Uint8Array
+ */
+export async function getCapturePng(id: string): Promise<Uint8Array> {
+  const buffer = await invoke<ArrayBuffer>("get_capture_png", { id });
+  return new Uint8Array(buffer);
+}
+
+/**
+ * Send raw RGBA pixels to a command over binary IPC (no JSON number arrays)
+ * Width, height and the command's other arguments travel in the x-rgba-args header
+ */
+export async function invokeWithRgba<T>(
+  command: string,
+  rgba: Uint8Array | Uint8ClampedArray,
+  width: number,
+  height: number,
+  args: Record<string, unknown> = {}
+): Promise<T> {
+  const body = new Uint8Array(rgba.buffer, rgba.byteOffset, rgba.byteLength);
+  const header = encodeURIComponent(JSON.stringify({ ...args, width, height }));
+  return await invoke<T>(command, body, { headers: { "x-rgba-args": header } });
+}
+
+/**
+ * Release a stored capture once it is no longer displayed
+ * @param id - Capture ID from a CaptureRef
+ */
+export async function releaseCapture(id: string): Promise<boolean> {
+  return await invoke<boolean>("release_capture", { id });
+}
+
 /**
  * Check if screen capture permission is granted (macOS)
  * @returns true if permission granted, false otherwise
@@ -212,17 +390,28 @@ export async function captureCursorMonitorHidden(): Promise<Uint8Array> {
 
 /**
  * Update global keyboard shortcuts in the backend
+ * Backend shortcut captures arrive as hotkey-capture-complete / hotkey-capture-failed events
  * @param capture - Hotkey for fullscreen capture
  * @param captureRegion - Hotkey for region capture
  * @param captureWindow - Hotkey for window capture
+ * @param captureActiveWindow - Hotkey that captures the focused window in the backend
+ * @param windowMatch - Hotkey that captures the best match for a window query in the backend
  * @returns Array of error messages for shortcuts that failed to register
  */
 export async function updateShortcuts(
   capture: string,
   captureRegion: string,
-  captureWindow: string
+  captureWindow: string,
+  captureActiveWindow?: string,
+  windowMatch?: { hotkey: string; query: WindowQuery; options?: WindowCaptureOptions }
 ): Promise<string[]> {
-  return await invoke<string[]>("update_shortcuts", { capture, captureRegion, captureWindow });
+  return await invoke<string[]>("update_shortcuts", {
+    capture,
+    captureRegion,
+    captureWindow,
+    captureActiveWindow,
+    windowMatch,
+  });
 }
 
 /**
@@ -266,6 +455,99 @@ export async function showOverlayWindowOnMonitor(monitorId: number): Promise<voi
   await invoke("show_overlay_window_on_monitor", { monitorId });
 }
 
+/**
+ * Show the window-picking overlay on the monitor under the cursor
+ * The overlay highlights the window under the mouse and emits "window-selected" on click
+ */
+export async function showWindowPicker(): Promise<void> {
+  const monitor = await getCursorMonitor();
+  await invoke("show_window_picker_on_monitor", { monitorId: monitor.id });
+}
+
+/**
+ * Capture the whole desktop and show a selection overlay on every monitor
+ * The selection may span monitors; the result arrives as "desktop-region-selected"
+ */
+export async function showDesktopOverlay(): Promise<void> {
+  await invoke("show_desktop_overlay");
+}
+
+/**
+ * Get the composed desktop image and layout for the desktop overlays
+ */
+export async function getDesktopOverlay(): Promise<DesktopOverlayData | null> {
+  return await invoke<DesktopOverlayData | null>("get_desktop_overlay");
+}
+
+/**
+ * Close the desktop overlays and cut the selected region from the desktop image
+ * @param region - Region in desktop canvas pixels, or null when cancelled
+ * @returns Reference to the stored region capture, or null when cancelled
+ */
+export async function finishDesktopOverlay(region: CaptureRegion | null): Promise<CaptureRef | null> {
+  return await invoke<CaptureRef | null>("finish_desktop_overlay", { ...region });
+}
+
+/**
+ * Capture all monitors stitched into one image by their desktop layout
+ * @param options - Gap fill and mixed-DPI scale; transparent gaps at the highest DPI when omitted
+ * @returns PNG image bytes as Uint8Array
+ */
+export async function captureAllScreens(options?: DesktopCaptureOptions): Promise<Uint8Array> {
+  const base64 = await invoke<string>("capture_all_screens", { options });
+  return base64ToBytes(base64);
+}
+
+/**
+ * Capture all monitors stitched into one image into the backend capture store
+ * @param options - Gap fill and mixed-DPI scale; transparent gaps at the highest DPI when omitted
+ * @returns Reference to the stored capture
+ */
+export async function captureAllScreensRef(options?: DesktopCaptureOptions): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_all_screens_ref", { options });
+}
+
+/**
+ * Capture all monitors stitched into one image (with window hidden)
+ * @returns PNG image bytes as Uint8Array
+ */
+export async function captureAllScreensHidden(options?: DesktopCaptureOptions): Promise<Uint8Array> {
+  return captureWithHiddenWindow(() => captureAllScreens(options));
+}
+
+/**
+ * Capture every monitor concurrently, one image per monitor
+ * Monitors that fail are reported in their own result instead of failing the call
+ * @param directory - Save files named after each monitor here instead of keeping captures in the store
+ * @param spec - File format when saving (PNG when omitted)
+ */
+export async function captureEachMonitor(
+  directory?: string,
+  spec?: ExportSpec
+): Promise<MonitorCaptureResult[]> {
+  return await invoke<MonitorCaptureResult[]>("capture_each_monitor", { directory, spec });
+}
+
+/**
+ * Get the monitor layout of the virtual desktop
+ */
+export async function getDesktopLayout(): Promise<DesktopLayout> {
+  return await invoke<DesktopLayout>("get_desktop_layout");
+}
+
+/**
+ * Capture a region in desktop coordinates, spanning monitors if needed
+ * @returns Reference to the stored capture
+ */
+export async function captureDesktopRegionRef(
+  x: number,
+  y: number,
+  width: number,
+  height: number
+): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("capture_desktop_region_ref", { x, y, width, height });
+}
+
 /**
  * Close overlay window
  */
@@ -275,9 +557,10 @@ export async function closeOverlayWindow(): Promise<void> {
 
 /**
  * Get screenshot data stored for overlay background
+ * @param captureId - Capture ID of the overlay background
  */
-export async function getScreenshotData(): Promise<string | null> {
-  return await invoke<string | null>("get_screenshot_data");
+export async function getScreenshotData(captureId: string): Promise<string | null> {
+  return await invoke<string | null>("get_screenshot_data", { captureId });
 }
 
 /**
@@ -288,8 +571,17 @@ export async function getOverlayMonitor(): Promise<{ id: number; width: number;
 }
 
 /**
- * Clear stored screenshot data
+ * Get the pickable windows of the current overlay, topmost first
+ * @returns Window geometry, or null when the overlay is selecting a region
+ */
+export async function getOverlayWindows(): Promise<OverlayWindowInfo[] | null> {
+  return await invoke<OverlayWindowInfo[] | null>("get_overlay_windows");
+}
+
+/**
+ * Release stored screenshot data
+ * @param captureId - Capture ID of the overlay background
  */
-export async function clearScreenshotData(): Promise<void> {
-  await invoke("clear_screenshot_data");
+export async function clearScreenshotData(captureId: string): Promise<void> {
+  await invoke("clear_screenshot_data", { captureId });
 }
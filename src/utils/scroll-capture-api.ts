@@ -0,0 +1,44 @@
+// Scrolling capture API - Tauri IPC wrapper
+// The backend grabs a region while the user scrolls and stitches the frames; results
+// arrive through "scroll-capture-progress" (ScrollCaptureProgress),
+// "scroll-capture-finished" (CaptureRef) and "scroll-capture-failed" (error string)
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CaptureRef } from "../types/screenshot";
+
+export interface ScrollCaptureOptions {
+  monitor_id: number;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  interval_ms?: number;
+}
+
+export interface ScrollCaptureProgress {
+  frames: number;
+  height: number;
+  outcome: "appended" | "unchanged" | "no_overlap";
+}
+
+/**
+ * Start capturing a region (coordinates relative to the monitor) while the user scrolls
+ */
+export async function startScrollCapture(options: ScrollCaptureOptions): Promise<void> {
+  await invoke("start_scroll_capture", { options });
+}
+
+/**
+ * Stop capturing; the stitched image is delivered via scroll-capture-finished
+ * @returns true if a session was running
+ */
+export async function stopScrollCapture(): Promise<boolean> {
+  return await invoke<boolean>("stop_scroll_capture");
+}
+
+/**
+ * Stitch stored captures (in scroll order) into one tall capture
+ */
+export async function stitchCaptures(captureIds: string[]): Promise<CaptureRef> {
+  return await invoke<CaptureRef>("stitch_captures", { captureIds });
+}